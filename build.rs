@@ -0,0 +1,69 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// With the `embed-inputs` feature, list every `input/yearYYYY/dayNN.txt` that exists so
+// the binary can carry its inputs with `include_str!`. Without it the list is empty.
+fn main() {
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("Cargo sets OUT_DIR"));
+    let manifest_dir =
+        PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("Cargo sets CARGO_MANIFEST_DIR"));
+
+    let mut inputs = vec![];
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        let input_dir = manifest_dir.join("input");
+        println!("cargo:rerun-if-changed={}", input_dir.display());
+        inputs = find_inputs(&input_dir);
+    }
+
+    let mut code = String::from("pub const INPUTS: &[(u32, u8, &str)] = &[\n");
+    for (year, day, path) in inputs {
+        println!("cargo:rerun-if-changed={}", path.display());
+        code.push_str(&format!(
+            "    ({year}, {day}, include_str!({:?})),\n",
+            path.display().to_string()
+        ));
+    }
+    code.push_str("];\n");
+
+    fs::write(out_dir.join("embedded_inputs.rs"), code).expect("OUT_DIR is writable");
+}
+
+// Every `yearYYYY/dayNN.txt` under `input_dir`, in year and day order.
+fn find_inputs(input_dir: &Path) -> Vec<(u32, u8, PathBuf)> {
+    let mut inputs = vec![];
+    let Ok(years) = fs::read_dir(input_dir) else {
+        return inputs;
+    };
+
+    for year_entry in years.flatten() {
+        let year_name = year_entry.file_name();
+        let Some(year) = year_name
+            .to_str()
+            .and_then(|name| name.strip_prefix("year"))
+            .and_then(|year| year.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        println!("cargo:rerun-if-changed={}", year_entry.path().display());
+
+        let Ok(days) = fs::read_dir(year_entry.path()) else {
+            continue;
+        };
+        for day_entry in days.flatten() {
+            let day_name = day_entry.file_name();
+            let Some(day) = day_name
+                .to_str()
+                .and_then(|name| name.strip_prefix("day"))
+                .and_then(|name| name.strip_suffix(".txt"))
+                .and_then(|day| day.parse::<u8>().ok())
+            else {
+                continue;
+            };
+            inputs.push((year, day, day_entry.path()));
+        }
+    }
+
+    inputs.sort();
+    return inputs;
+}
@@ -0,0 +1,277 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+// Everything that can go wrong while reading and parsing a puzzle input.
+#[derive(Debug)]
+pub enum Error {
+    // The input couldn't be read.
+    Io(io::Error),
+    // A line doesn't have the expected format. `line` and `column` count from 1 and
+    // `column` is `None` when the line as a whole is at fault.
+    Parse {
+        line: usize,
+        column: Option<usize>,
+        text: String,
+        message: String,
+    },
+    // The input as a whole is malformed, such as a missing section.
+    Input(String),
+    // The input of a day isn't in the input directory and there's no way to download it.
+    MissingInput {
+        year: u32,
+        day: u8,
+        path: PathBuf,
+    },
+    // Downloading the input of a day failed.
+    Fetch {
+        year: u32,
+        day: u8,
+        message: String,
+    },
+    // A downloaded input was changed after it was cached.
+    Checksum {
+        path: PathBuf,
+    },
+}
+
+impl Error {
+    // The offending line with a caret under the offending column, for diagnostics.
+    pub fn snippet(&self) -> Option<String> {
+        let Error::Parse {
+            line, column, text, ..
+        } = self
+        else {
+            return None;
+        };
+
+        let gutter = " ".repeat(line.to_string().len());
+        let mut snippet = format!("{gutter} |\n{line} | {text}\n{gutter} |");
+        if let Some(column) = column {
+            snippet.push_str(&format!(" {}^", " ".repeat(column - 1)));
+        }
+        return Some(snippet);
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Error::Io(error) => write!(f, "{error}"),
+            Error::Parse {
+                line,
+                column: Some(column),
+                message,
+                ..
+            } => write!(f, "line {line}, column {column}: {message}"),
+            Error::Parse {
+                line,
+                column: None,
+                message,
+                ..
+            } => write!(f, "line {line}: {message}"),
+            Error::Input(message) => write!(f, "{message}"),
+            Error::MissingInput { year, day, path } => write!(
+                f,
+                "the input of {year} day {day:02} is missing, expected it at {}. \
+                 Set AOC_SESSION to your session cookie to download it",
+                path.display()
+            ),
+            Error::Fetch { year, day, message } => {
+                write!(f, "failed to download the input of {year} day {day:02}: {message}")
+            }
+            Error::Checksum { path } => write!(
+                f,
+                "{} doesn't match the checksum it was downloaded with, delete it to download it again",
+                path.display()
+            ),
+        };
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        return Error::Io(error);
+    }
+}
+
+// A line of input along with its line number, so parsers can point at what's wrong.
+// The text borrows from the input, so splitting an input into lines doesn't copy it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl Line<'_> {
+    // An error about the line as a whole.
+    pub fn error(&self, message: impl Into<String>) -> Error {
+        return Error::Parse {
+            line: self.number,
+            column: None,
+            text: self.text.to_string(),
+            message: message.into(),
+        };
+    }
+
+    // An error about the character at `index`, counting chars from 0.
+    pub fn error_at_char(&self, index: usize, message: impl Into<String>) -> Error {
+        return Error::Parse {
+            line: self.number,
+            column: Some(index + 1),
+            text: self.text.to_string(),
+            message: message.into(),
+        };
+    }
+
+    // An error about `token`, which must be a slice of this line's text.
+    pub fn error_at(&self, token: &str, message: impl Into<String>) -> Error {
+        let start = self.text.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| *offset <= self.text.len());
+
+        return match offset {
+            Some(offset) => self.error_at_char(self.text[..offset].chars().count(), message),
+            None => self.error(message),
+        };
+    }
+
+    // Unwrap a field split out of this line, or report what was expected at the end
+    // of the line.
+    pub fn expect<'a>(&self, field: Option<&'a str>, what: &str) -> Result<&'a str, Error> {
+        return field.ok_or_else(|| {
+            self.error_at_char(self.text.chars().count(), format!("expected {what}"))
+        });
+    }
+
+    // Parse `token`, which must be a slice of this line's text, as a `T`.
+    pub fn parse<T>(&self, token: &str) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        return token
+            .parse::<T>()
+            .map_err(|error| self.error_at(token, format!("invalid value '{token}': {error}")));
+    }
+}
+
+// Number the lines of an input from 1. Lines can end with "\n" or "\r\n".
+pub fn numbered(input: &str) -> impl Iterator<Item = Line<'_>> {
+    return input.lines().enumerate().map(|(index, text)| {
+        return Line {
+            number: index + 1,
+            text,
+        };
+    });
+}
+
+// Take the next line, or report what was expected when the input ends early.
+pub fn next_line<'a>(
+    lines: &mut impl Iterator<Item = Line<'a>>,
+    what: &str,
+) -> Result<Line<'a>, Error> {
+    return lines
+        .next()
+        .ok_or_else(|| Error::Input(format!("expected {what} but the input ended")));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINE: Line = Line {
+        number: 7,
+        text: "move 12 to 3",
+    };
+
+    // Where `error` points, as (line, column).
+    fn position(error: &Error) -> (usize, Option<usize>) {
+        let Error::Parse { line, column, .. } = error else {
+            panic!("expected a parse error, got {error:?}");
+        };
+        return (*line, *column);
+    }
+
+    #[test]
+    fn error_at_points_at_the_token() {
+        let text = LINE.text;
+        assert_eq!(position(&LINE.error_at(&text[..4], "start")), (7, Some(1)));
+        assert_eq!(
+            position(&LINE.error_at(&text[5..7], "middle")),
+            (7, Some(6))
+        );
+        assert_eq!(position(&LINE.error_at(&text[11..], "end")), (7, Some(12)));
+        // An empty token after the last character points past the end of the line.
+        assert_eq!(
+            position(&LINE.error_at(&text[12..], "after")),
+            (7, Some(13))
+        );
+    }
+
+    #[test]
+    fn error_at_counts_chars_not_bytes() {
+        let line = Line {
+            number: 1,
+            text: "ÿé → 42",
+        };
+        let token = &line.text[line.text.find('4').unwrap()..];
+        assert_eq!(position(&line.error_at(token, "number")), (1, Some(6)));
+        assert_eq!(
+            line.parse::<u8>(&line.text[..2]).unwrap_err().to_string(),
+            "line 1, column 1: invalid value 'ÿ': invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn error_at_a_token_from_elsewhere_blames_the_line() {
+        let elsewhere = String::from("12");
+        assert_eq!(position(&LINE.error_at(&elsewhere, "elsewhere")), (7, None));
+    }
+
+    #[test]
+    fn error_at_char_on_an_empty_line() {
+        let line = Line {
+            number: 3,
+            text: "",
+        };
+        let error = line.error_at_char(0, "expected a number");
+        assert_eq!(error.to_string(), "line 3, column 1: expected a number");
+        assert_eq!(error.snippet().unwrap(), "  |\n3 | \n  | ^");
+        assert_eq!(
+            line.expect(None, "a number").unwrap_err().to_string(),
+            "line 3, column 1: expected a number"
+        );
+    }
+
+    #[test]
+    fn display_with_and_without_a_column() {
+        let error = LINE.error_at_char(5, "expected a letter");
+        assert_eq!(error.to_string(), "line 7, column 6: expected a letter");
+        assert_eq!(
+            error.snippet().unwrap(),
+            "  |\n7 | move 12 to 3\n  |      ^"
+        );
+
+        let error = LINE.error("expected three words");
+        assert_eq!(error.to_string(), "line 7: expected three words");
+        assert_eq!(error.snippet().unwrap(), "  |\n7 | move 12 to 3\n  |");
+    }
+
+    #[test]
+    fn snippet_widens_the_gutter_for_long_line_numbers() {
+        let line = Line {
+            number: 120,
+            text: "abc",
+        };
+        assert_eq!(
+            line.error_at_char(2, "bad").snippet().unwrap(),
+            "    |\n120 | abc\n    |   ^"
+        );
+        assert_eq!(Error::Input("missing".to_string()).snippet(), None);
+    }
+}
@@ -0,0 +1,1221 @@
+use crate::util::direction::Direction;
+use crate::util::grid::Grid;
+use crate::util::point::Point;
+use crate::util::random::Rng;
+use crate::util::search;
+use std::collections::HashSet;
+
+// Makes an input for a day from a random number generator. What `size` counts depends
+// on the day, such as lines, reports or the side of a grid, and `default_size` is about
+// the size of a real input.
+pub struct Generator {
+    pub day: u8,
+    pub default_size: usize,
+    pub generate: fn(&mut Rng, usize) -> String,
+}
+
+pub const GENERATORS: [Generator; 25] = [
+    Generator {
+        day: 1,
+        default_size: 1000,
+        generate: location_lists,
+    },
+    Generator {
+        day: 2,
+        default_size: 1000,
+        generate: reports,
+    },
+    Generator {
+        day: 3,
+        default_size: 700,
+        generate: corrupted_memory,
+    },
+    Generator {
+        day: 4,
+        default_size: 140,
+        generate: word_search,
+    },
+    Generator {
+        day: 5,
+        default_size: 200,
+        generate: print_queue,
+    },
+    Generator {
+        day: 6,
+        default_size: 130,
+        generate: lab_map,
+    },
+    Generator {
+        day: 7,
+        default_size: 850,
+        generate: calibrations,
+    },
+    Generator {
+        day: 8,
+        default_size: 50,
+        generate: antenna_map,
+    },
+    Generator {
+        day: 9,
+        default_size: 10000,
+        generate: |rng, files| disk_map(rng, files, 9),
+    },
+    Generator {
+        day: 10,
+        default_size: 50,
+        generate: |rng, side| topographic_map(rng, side, side).to_string(),
+    },
+    Generator {
+        day: 11,
+        default_size: 8,
+        generate: stones,
+    },
+    Generator {
+        day: 12,
+        default_size: 140,
+        generate: garden,
+    },
+    Generator {
+        day: 13,
+        default_size: 320,
+        generate: claw_machines,
+    },
+    Generator {
+        day: 14,
+        default_size: 500,
+        generate: robots,
+    },
+    Generator {
+        day: 15,
+        default_size: 50,
+        generate: warehouse,
+    },
+    Generator {
+        day: 16,
+        default_size: 141,
+        generate: reindeer_maze,
+    },
+    Generator {
+        day: 17,
+        default_size: 16,
+        generate: program,
+    },
+    Generator {
+        day: 18,
+        default_size: 3450,
+        generate: falling_bytes,
+    },
+    Generator {
+        day: 19,
+        default_size: 400,
+        generate: towels,
+    },
+    Generator {
+        day: 20,
+        default_size: 141,
+        generate: |rng, side| racetrack(rng, side, side).to_string(),
+    },
+    Generator {
+        day: 21,
+        default_size: 5,
+        generate: door_codes,
+    },
+    Generator {
+        day: 22,
+        default_size: 2000,
+        generate: secrets,
+    },
+    Generator {
+        day: 23,
+        default_size: 520,
+        generate: network,
+    },
+    Generator {
+        day: 24,
+        default_size: 45,
+        generate: |rng, bits| adder(rng, bits, 4),
+    },
+    Generator {
+        day: 25,
+        default_size: 500,
+        generate: schematics,
+    },
+];
+
+// The input for `day` made from `seed`, about as big as a real input unless `size` is
+// given. The same seed and size always make the same input.
+pub fn generate(day: u8, seed: u64, size: Option<usize>) -> Option<String> {
+    let generator = GENERATORS.iter().find(|generator| generator.day == day)?;
+    let size = size.unwrap_or(generator.default_size).max(1);
+    return Some((generator.generate)(&mut Rng::new(seed), size));
+}
+
+// Two columns of five digit location IDs. Half of the right column repeats IDs from the
+// left column, so the similarity score isn't zero.
+fn location_lists(rng: &mut Rng, pairs: usize) -> String {
+    let left: Vec<usize> = (0..pairs).map(|_| rng.between(10000, 99999)).collect();
+
+    let mut input = String::new();
+    for id in &left {
+        let right = match rng.one_in(2) {
+            true => *rng.choose(&left),
+            false => rng.between(10000, 99999),
+        };
+        input.push_str(&format!("{id}   {right}\n"));
+    }
+    return input;
+}
+
+// Reports of 5 to 8 levels that all go up or all go down by 1 to 3. About half of them
+// have a level that's moved, which may make the report unsafe.
+fn reports(rng: &mut Rng, count: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..count {
+        let direction = if rng.one_in(2) { 1 } else { -1 };
+        let mut level = rng.between(30, 70) as i32;
+
+        let mut levels = vec![];
+        for _ in 0..rng.between(5, 8) {
+            levels.push(level);
+            level += direction * rng.between(1, 3) as i32;
+        }
+        if rng.one_in(2) {
+            let index = rng.below(levels.len());
+            levels[index] += rng.between(0, 8) as i32 - 4;
+        }
+
+        let levels: Vec<String> = levels.iter().map(|level| level.to_string()).collect();
+        input.push_str(&levels.join(" "));
+        input.push('\n');
+    }
+    return input;
+}
+
+const NOISE: [&str; 23] = [
+    "what()", "from()", "select()", "who()", "how()", "where()", " ", "'", "!", "@", "#", "$", "%",
+    "^", "&", "*", "[", "]", "{", "}", "<", ">", ",",
+];
+
+// Instructions that are almost, but not quite, a multiplication or a do() or don't().
+const DECOYS: [&str; 8] = [
+    "mul(4*",
+    "mul[3,7]",
+    "mul ( 2 , 4 )",
+    "mul(1234,5)",
+    "mul(6,9!",
+    "?(12,34)",
+    "don't",
+    "do(",
+];
+
+// Memory with `instructions` multiplications of two numbers up to 999 among noise and
+// decoys, with do() and don't() now and then. It's split over lines of 120
+// multiplications like the real memory.
+fn corrupted_memory(rng: &mut Rng, instructions: usize) -> String {
+    let mut input = String::new();
+    for instruction in 0..instructions {
+        for _ in 0..rng.below(4) {
+            input.push_str(rng.choose::<&str>(&NOISE));
+        }
+        if rng.one_in(3) {
+            input.push_str(rng.choose::<&str>(&DECOYS));
+        }
+        if rng.one_in(10) {
+            input.push_str(rng.choose::<&str>(&["do()", "don't()"]));
+        }
+
+        let (x, y) = (rng.between(1, 999), rng.between(1, 999));
+        input.push_str(&format!("mul({x},{y})"));
+        if instruction % 120 == 119 || instruction + 1 == instructions {
+            input.push('\n');
+        }
+    }
+    return input;
+}
+
+// A square of random X, M, A and S letters.
+fn word_search(rng: &mut Rng, side: usize) -> String {
+    return Grid::from_fn(side, side, |_| *rng.choose(&['X', 'M', 'A', 'S'])).to_string();
+}
+
+// Rules for every pair of 49 pages, so they put the pages in a single order like the real
+// rules do, and updates of 5 to 23 of those pages. About half of the updates are already
+// in the right order.
+fn print_queue(rng: &mut Rng, updates: usize) -> String {
+    let mut order: Vec<usize> = (10..100).collect();
+    rng.shuffle(&mut order);
+    order.truncate(49);
+
+    let mut rules = vec![];
+    for (index, before) in order.iter().enumerate() {
+        for after in &order[index + 1..] {
+            rules.push(format!("{before}|{after}\n"));
+        }
+    }
+    rng.shuffle(&mut rules);
+
+    let mut input = rules.concat();
+    input.push('\n');
+    for _ in 0..updates {
+        let mut pages = order.clone();
+        rng.shuffle(&mut pages);
+        pages.truncate(rng.between(2, 11) * 2 + 1);
+        if rng.one_in(2) {
+            pages.sort_by_key(|page| order.iter().position(|ordered| ordered == page));
+        }
+
+        let pages: Vec<String> = pages.iter().map(|page| page.to_string()).collect();
+        input.push_str(&pages.join(","));
+        input.push('\n');
+    }
+    return input;
+}
+
+// A lab with an obstacle on about one in twenty positions and the guard facing up. The
+// guard always walks out of the lab, as the puzzle promises.
+fn lab_map(rng: &mut Rng, side: usize) -> String {
+    loop {
+        let mut lab = Grid::from_fn(side, side, |_| if rng.one_in(20) { '#' } else { '.' });
+        let guard = Point::new(rng.below(side), rng.below(side));
+        lab[guard] = '^';
+
+        if guard_leaves(&lab, guard) {
+            return lab.to_string();
+        }
+    }
+}
+
+fn guard_leaves(lab: &Grid<char>, guard: Point) -> bool {
+    let mut seen = HashSet::new();
+    let (mut position, mut direction) = (guard, Direction::Up);
+
+    while seen.insert((position, direction)) {
+        match lab.step(position, direction) {
+            None => return true,
+            Some(next) if lab[next] == '#' => direction = direction.clockwise(),
+            Some(next) => position = next,
+        }
+    }
+    return false;
+}
+
+// Equations of 2 to 12 numbers up to 999. A third of them are solved with + and *, a
+// third also need || and the rest have a test value that's a little off. Test values
+// stay below 10^14 so the solution's concatenation can't overflow.
+fn calibrations(rng: &mut Rng, equations: usize) -> String {
+    let mut input = String::new();
+    let mut count = 0;
+    while count < equations {
+        let numbers: Vec<u64> = (0..rng.between(2, 12))
+            .map(|_| match rng.one_in(4) {
+                true => rng.between(100, 999) as u64,
+                false => rng.between(1, 99) as u64,
+            })
+            .collect();
+        let kind = rng.below(3);
+
+        let mut total = Some(numbers[0]);
+        for &number in &numbers[1..] {
+            let operator = rng.below(if kind == 0 { 2 } else { 3 });
+            total = total.and_then(|total| {
+                return match operator {
+                    0 => total.checked_add(number),
+                    1 => total.checked_mul(number),
+                    _ => total
+                        .checked_mul(10u64.pow(number.to_string().len() as u32))?
+                        .checked_add(number),
+                };
+            });
+        }
+        let Some(mut total) = total.filter(|&total| total < 100_000_000_000_000) else {
+            continue;
+        };
+        if kind == 2 {
+            total += rng.between(1, 9) as u64;
+        }
+
+        let numbers: Vec<String> = numbers.iter().map(|number| number.to_string()).collect();
+        input.push_str(&format!("{total}: {}\n", numbers.join(" ")));
+        count += 1;
+    }
+    return input;
+}
+
+const FREQUENCIES: &str = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// A roof with three or four antennas of each frequency, and about as many frequencies for
+// its size as the real roof.
+fn antenna_map(rng: &mut Rng, side: usize) -> String {
+    let mut roof = Grid::new_filled(side, side, '.');
+    let mut points: Vec<Point> = roof.points().collect();
+    rng.shuffle(&mut points);
+    let mut points = points.into_iter();
+
+    let mut frequencies: Vec<char> = FREQUENCIES.chars().collect();
+    rng.shuffle(&mut frequencies);
+    for &frequency in frequencies.iter().take((side * side / 60).max(1)) {
+        let antennas = rng.between(3, 4);
+        for point in points.by_ref().take(antennas) {
+            roof[point] = frequency;
+        }
+    }
+    return roof.to_string();
+}
+
+// A disk map of `files` files of 1 to `max_file_size` blocks, with up to 9 free blocks
+// after each one but the last.
+pub fn disk_map(rng: &mut Rng, files: usize, max_file_size: usize) -> String {
+    let mut disk_map = String::new();
+    for file in 0..files {
+        disk_map.push_str(&rng.between(1, max_file_size).to_string());
+        if file + 1 < files {
+            disk_map.push_str(&rng.between(0, 9).to_string());
+        }
+    }
+    disk_map.push('\n');
+    return disk_map;
+}
+
+// A map that mostly climbs towards the bottom right, so it's full of trails that branch
+// and join again.
+pub fn topographic_map(rng: &mut Rng, row_count: usize, col_count: usize) -> Grid<u32> {
+    return Grid::from_fn(row_count, col_count, |point| {
+        return ((point.x + point.y + rng.below(2)) % 10) as u32;
+    });
+}
+
+// A row of stones engraved with numbers of one to seven digits.
+fn stones(rng: &mut Rng, count: usize) -> String {
+    let stones: Vec<String> = (0..count)
+        .map(|_| {
+            let digits = rng.between(1, 7) as u32;
+            return rng.below(10usize.pow(digits)).to_string();
+        })
+        .collect();
+    return stones.join(" ") + "\n";
+}
+
+// A garden where each plot mostly grows the plant to its left or above it, with a new
+// plant now and then. Regions of the same plant touch, wind around each other and
+// enclose other regions, as they do in the real garden.
+fn garden(rng: &mut Rng, side: usize) -> String {
+    let plants: Vec<char> = ('A'..='Z').collect();
+    let mut garden = Grid::new_filled(side, side, 'A');
+
+    for y in 0..side {
+        for x in 0..side {
+            let left = x.checked_sub(1).map(|x| garden[Point::new(x, y)]);
+            let up = y.checked_sub(1).map(|y| garden[Point::new(x, y)]);
+
+            garden[Point::new(x, y)] = match (left, up) {
+                _ if rng.one_in(8) => *rng.choose(&plants),
+                (Some(left), Some(up)) => *rng.choose(&[left, up]),
+                (Some(plant), None) | (None, Some(plant)) => plant,
+                (None, None) => *rng.choose(&plants),
+            };
+        }
+    }
+    return garden.to_string();
+}
+
+// How much further away the prizes are in part 2.
+const PRIZE_OFFSET: usize = 10_000_000_000_000;
+
+// Claw machines with buttons that move the claw 10 to 99 along each axis. A third of the
+// prizes can be won with up to 100 presses of each button, a third can be won once they're
+// moved for part 2, and the rest are moved along the X axis from a position that can be
+// won.
+fn claw_machines(rng: &mut Rng, count: usize) -> String {
+    let mut machines = vec![];
+    while machines.len() < count {
+        let [a_x, a_y, b_x, b_y] = [(); 4].map(|_| rng.between(10, 99));
+        // The solution can't tell how many presses win when the buttons move the claw in
+        // the same direction.
+        if a_x * b_y == a_y * b_x {
+            continue;
+        }
+
+        let kind = rng.below(3);
+        let (a, b) = match kind {
+            1 => {
+                // The presses that reach a little past the moved prize position.
+                let target = [(); 2].map(|_| (PRIZE_OFFSET + rng.between(1000, 20000)) as f64);
+                let [a_x, a_y, b_x, b_y] = [a_x, a_y, b_x, b_y].map(|value| value as f64);
+                let determinant = a_x * b_y - a_y * b_x;
+                let a = ((target[0] * b_y - target[1] * b_x) / determinant).floor();
+                let b = ((a_x * target[1] - a_y * target[0]) / determinant).floor();
+                if a < 0.0 || b < 0.0 {
+                    continue;
+                }
+                (a as usize, b as usize)
+            }
+            _ => (rng.between(0, 100), rng.between(0, 100)),
+        };
+        let (mut prize_x, mut prize_y) = (a * a_x + b * b_x, a * a_y + b * b_y);
+        match kind {
+            0 => {}
+            1 => {
+                prize_x -= PRIZE_OFFSET;
+                prize_y -= PRIZE_OFFSET;
+            }
+            _ => prize_x += rng.between(1, 99),
+        }
+
+        machines.push(format!(
+            "Button A: X+{a_x}, Y+{a_y}\nButton B: X+{b_x}, Y+{b_y}\nPrize: X={prize_x}, Y={prize_y}\n"
+        ));
+    }
+    return machines.join("\n");
+}
+
+// The size of the bathroom and the number of rows of the Christmas tree the robots form.
+const BATHROOM: (usize, usize) = (101, 103);
+const TREE_ROWS: usize = 18;
+
+// Robots that form a Christmas tree of 324 robots in the top left quadrant at some second,
+// and are spread out the rest of the time. There are always enough robots for the tree.
+// Part 2 looks for a quadrant with over 310 robots, so with many more than the real 500
+// robots it finds a crowded quadrant before the tree.
+fn robots(rng: &mut Rng, count: usize) -> String {
+    let second = rng.between(1, BATHROOM.0 * BATHROOM.1 - 1);
+    return robots_with_tree(rng, count, second);
+}
+
+fn robots_with_tree(rng: &mut Rng, count: usize, second: usize) -> String {
+    let (width, height) = BATHROOM;
+
+    // Where the robots are at `second`.
+    let mut positions = vec![];
+    for row in 0..TREE_ROWS {
+        for col in 0..row * 2 + 1 {
+            positions.push((25 + col - row, 10 + row));
+        }
+    }
+    while positions.len() < count {
+        positions.push((rng.below(width), rng.below(height)));
+    }
+    rng.shuffle(&mut positions);
+
+    let mut input = String::new();
+    for (x, y) in positions {
+        // Every robot moves along both axes, so no robot stays in the tree.
+        let [dx, dy] = [(); 2].map(|_| {
+            let speed = rng.between(1, 99) as isize;
+            return if rng.one_in(2) { speed } else { -speed };
+        });
+        let x = (x as isize - dx * second as isize).rem_euclid(width as isize);
+        let y = (y as isize - dy * second as isize).rem_euclid(height as isize);
+        input.push_str(&format!("p={x},{y} v={dx},{dy}\n"));
+    }
+    return input;
+}
+
+// A walled warehouse with boxes on about a quarter of the floor and a few walls, followed
+// by `side * 400` moves for the robot in lines of 1000.
+fn warehouse(rng: &mut Rng, side: usize) -> String {
+    let side = side.max(3);
+    let mut map = Grid::from_fn(side, side, |point| {
+        let is_edge = point.x == 0 || point.y == 0 || point.x == side - 1 || point.y == side - 1;
+        if is_edge || rng.one_in(20) {
+            return '#';
+        }
+        return if rng.one_in(4) { 'O' } else { '.' };
+    });
+    map[Point::new(side / 2, side / 2)] = '@';
+
+    let mut input = map.to_string();
+    input.push('\n');
+    let moves = side * 400;
+    for index in 0..moves {
+        input.push(rng.choose(&Direction::CARDINAL).arrow());
+        if index % 1000 == 999 || index + 1 == moves {
+            input.push('\n');
+        }
+    }
+    return input;
+}
+
+// Carve a maze on the odd rows and columns of a grid of walls, so every corridor is one
+// tile wide and there's exactly one way between any two tiles. Also returns the tile each
+// tile was carved from, which leads back to the top left corner.
+fn carve_maze(
+    rng: &mut Rng,
+    cells_across: usize,
+    cells_down: usize,
+) -> (Grid<char>, Grid<Option<Point>>) {
+    let mut maze = Grid::new_filled(cells_down * 2 + 1, cells_across * 2 + 1, '#');
+    let mut came_from: Grid<Option<Point>> = maze.map(|_| None);
+
+    let start = Point::new(1, 1);
+    maze[start] = '.';
+    let mut stack = vec![start];
+    while let Some(&cell) = stack.last() {
+        let unvisited: Vec<Direction> =
+            Direction::CARDINAL
+                .into_iter()
+                .filter(|direction| {
+                    return maze.neighbor(cell, direction.offset() * 2).is_some_and(
+                        |(next, &tile)| tile == '#' && next.x % 2 == 1 && next.y % 2 == 1,
+                    );
+                })
+                .collect();
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let offset = rng.choose(&unvisited).offset();
+        let (wall, _) = maze
+            .neighbor(cell, offset)
+            .expect("The wall is inside the maze");
+        let (next, _) = maze
+            .neighbor(cell, offset * 2)
+            .expect("The cell is inside the maze");
+        maze[wall] = '.';
+        maze[next] = '.';
+        came_from[wall] = Some(cell);
+        came_from[next] = Some(wall);
+        stack.push(next);
+    }
+
+    return (maze, came_from);
+}
+
+// A maze with the start in the bottom left corner and the end in the top right, like the
+// real one. Some walls between corridors are knocked down so there are several routes.
+fn reindeer_maze(rng: &mut Rng, side: usize) -> String {
+    let cells = (side.max(3) - 1) / 2;
+    let (mut maze, _) = carve_maze(rng, cells, cells);
+
+    for _ in 0..cells * cells / 8 {
+        let wall = Point::new(
+            rng.between(1, maze.col_count - 2),
+            rng.between(1, maze.row_count - 2),
+        );
+        // Walls with an odd and an even coordinate are the ones between two corridors.
+        if (wall.x + wall.y) % 2 == 1 {
+            maze[wall] = '.';
+        }
+    }
+
+    let (start, end) = (
+        Point::new(1, maze.row_count - 2),
+        Point::new(maze.col_count - 2, 1),
+    );
+    maze[start] = 'S';
+    maze[end] = 'E';
+    return maze.to_string();
+}
+
+// A racetrack with a single path from the start in the top left corner to the end at the
+// tile farthest from it. The track is carved over the whole grid inside the outer wall,
+// not just on the odd rows and columns like `carve_maze`, so it can touch itself at a
+// corner and allow cheats that turn around it.
+pub fn racetrack(rng: &mut Rng, col_count: usize, row_count: usize) -> Grid<char> {
+    // At least two tiles fit inside the wall, so the start and the end are apart.
+    let (col_count, row_count) = (col_count.max(4), row_count.max(3));
+    let inside = |point: Point| {
+        return (1..col_count - 1).contains(&point.x) && (1..row_count - 1).contains(&point.y);
+    };
+
+    let mut maze = Grid::new_filled(row_count, col_count, '#');
+    let mut came_from: Grid<Option<Point>> = maze.map(|_| None);
+
+    let start = Point::new(1, 1);
+    maze[start] = '.';
+    let mut stack = vec![start];
+    while let Some(&tile) = stack.last() {
+        // A wall can only be carved when it touches no other part of the track, so the
+        // track never runs right alongside itself.
+        let walls: Vec<Point> = maze
+            .neighbors4(tile)
+            .filter(|&(next, &value)| value == '#' && inside(next))
+            .map(|(next, _)| next)
+            .filter(|&next| {
+                return maze
+                    .neighbors4(next)
+                    .all(|(neighbor, &value)| value == '#' || neighbor == tile);
+            })
+            .collect();
+        if walls.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let next = *rng.choose(&walls);
+        maze[next] = '.';
+        came_from[next] = Some(tile);
+        stack.push(next);
+    }
+
+    let distances = search::bfs(
+        start,
+        |&point| {
+            return maze
+                .neighbors4(point)
+                .filter(|(_, &tile)| tile != '#')
+                .map(|(next, _)| next);
+        },
+        |_| false,
+    )
+    .distances;
+    let (&end, _) = distances
+        .iter()
+        .max_by_key(|&(point, &distance)| (distance, point.y, point.x))
+        .expect("The start is in the maze");
+
+    let mut track = Grid::new_filled(row_count, col_count, '#');
+    let mut point = end;
+    while let Some(previous) = came_from[point] {
+        track[point] = '.';
+        point = previous;
+    }
+    track[start] = 'S';
+    track[end] = 'E';
+    return track;
+}
+
+// Programs like the real ones, which output a value made from the lowest three bits of
+// register A and some higher bits, then shift A three bits right until it's zero. A
+// starts with `digits` three bit digits, so the first run outputs that many values.
+// Only programs with a value of A that makes them output themselves are made.
+fn program(rng: &mut Rng, digits: usize) -> String {
+    return program_with_copy(rng, digits).0;
+}
+
+// A program along with the lowest value of register A that makes it output itself.
+fn program_with_copy(rng: &mut Rng, digits: usize) -> (String, u64) {
+    let digits = digits.clamp(1, 21);
+    loop {
+        let [x, y, ignored] = [(); 3].map(|_| rng.below(8) as u64);
+        let program = match rng.below(3) {
+            0 => [2, 4, 1, x, 7, 5, 1, y, 4, ignored, 5, 5, 0, 3, 3, 0],
+            1 => [2, 4, 1, x, 7, 5, 4, ignored, 1, y, 5, 5, 0, 3, 3, 0],
+            _ => [2, 4, 1, x, 7, 5, 0, 3, 1, y, 4, ignored, 5, 5, 3, 0],
+        };
+        let Some(copy) = lowest_copy(&program, x, y, 0) else {
+            continue;
+        };
+
+        let a = rng.between(1 << (3 * (digits - 1)), (1 << (3 * digits)) - 1);
+        let program: Vec<String> = program.iter().map(|value| value.to_string()).collect();
+        let input = format!(
+            "Register A: {a}\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n",
+            program.join(",")
+        );
+        return (input, copy);
+    }
+}
+
+// The value the program outputs in the loop that starts with `a` in register A. Every
+// program layout computes the same value.
+fn loop_output(a: u64, x: u64, y: u64) -> u64 {
+    let b = (a % 8) ^ x;
+    return (b ^ y ^ (a >> b)) % 8;
+}
+
+// The lowest value of register A that starts with the digits of `a` and outputs
+// `program`. Each loop drops the lowest digit, so the last output only depends on the
+// highest digit and the digits are picked from the highest down.
+fn lowest_copy(program: &[u64], x: u64, y: u64, a: u64) -> Option<u64> {
+    let Some((&last, rest)) = program.split_last() else {
+        return Some(a);
+    };
+
+    return (0..8).find_map(|digit| {
+        let next = (a << 3) | digit;
+        if next == 0 || loop_output(next, x, y) != last {
+            return None;
+        }
+        return lowest_copy(rest, x, y, next);
+    });
+}
+
+// The memory space and how many bytes fall before part 1.
+const MEMORY_SIDE: usize = 71;
+const FALLEN_BYTES: usize = 1024;
+
+// Bytes that fall on different positions of the memory space, never on the start. The
+// exit can still be reached after the first 1024 bytes, as part 1 expects, and `count` is
+// raised when needed so the bytes end up blocking the exit.
+fn falling_bytes(rng: &mut Rng, count: usize) -> String {
+    return falling_bytes_with_blocker(rng, count).0;
+}
+
+// Falling bytes along with the first byte that blocks the exit.
+fn falling_bytes_with_blocker(rng: &mut Rng, count: usize) -> (String, Point) {
+    let start = Point::new(0, 0);
+    loop {
+        let mut bytes: Vec<Point> = Grid::new_filled(MEMORY_SIDE, MEMORY_SIDE, ())
+            .points()
+            .filter(|&point| point != start)
+            .collect();
+        rng.shuffle(&mut bytes);
+
+        // The exit is reachable after `lower` bytes and blocked after `upper` bytes. Every
+        // position is in the list, including the exit, so all of them block it.
+        let (mut lower, mut upper) = (0, bytes.len());
+        while upper - lower > 1 {
+            let middle = lower + (upper - lower) / 2;
+            match exit_reachable(&bytes[..middle]) {
+                true => lower = middle,
+                false => upper = middle,
+            }
+        }
+        if upper <= FALLEN_BYTES {
+            continue;
+        }
+
+        let input: String = bytes[..count.max(upper)]
+            .iter()
+            .map(|byte| format!("{},{}\n", byte.x, byte.y))
+            .collect();
+        return (input, bytes[upper - 1]);
+    }
+}
+
+fn exit_reachable(bytes: &[Point]) -> bool {
+    let mut memory = Grid::new_filled(MEMORY_SIDE, MEMORY_SIDE, '.');
+    for &byte in bytes {
+        memory[byte] = '#';
+    }
+
+    let exit = Point::new(MEMORY_SIDE - 1, MEMORY_SIDE - 1);
+    let search = search::bfs(
+        Point::new(0, 0),
+        |&point| {
+            return memory
+                .neighbors4(point)
+                .filter(|(_, &tile)| tile == '.')
+                .map(|(next, _)| next);
+        },
+        |&point| point == exit,
+    );
+    return search.goal_distance().is_some();
+}
+
+const COLORS: [char; 5] = ['w', 'u', 'b', 'r', 'g'];
+
+// Towel patterns of one to eight stripes, and designs of 20 to 60 stripes. One color has
+// no towel of a single stripe, so some designs can't be made. Half of the designs are
+// put together from towels, so they can.
+fn towels(rng: &mut Rng, designs: usize) -> String {
+    let missing = *rng.choose(&COLORS);
+    let mut patterns: Vec<String> = COLORS
+        .iter()
+        .filter(|&&color| color != missing)
+        .map(|color| color.to_string())
+        .collect();
+    let mut seen: HashSet<String> = patterns.iter().cloned().collect();
+    while patterns.len() < designs + designs / 8 + 8 {
+        let pattern: String = (0..rng.between(2, 8))
+            .map(|_| *rng.choose(&COLORS))
+            .collect();
+        if seen.insert(pattern.clone()) {
+            patterns.push(pattern);
+        }
+    }
+    rng.shuffle(&mut patterns);
+
+    let mut input = patterns.join(", ") + "\n\n";
+    for _ in 0..designs {
+        let length = rng.between(20, 60);
+        let mut design = String::new();
+        let from_towels = rng.one_in(2);
+        while design.len() < length {
+            match from_towels {
+                true => design.push_str(rng.choose(&patterns).as_str()),
+                false => design.push(*rng.choose(&COLORS)),
+            }
+        }
+        input.push_str(&design);
+        input.push('\n');
+    }
+    return input;
+}
+
+// Door codes of three digits followed by 'A'.
+fn door_codes(rng: &mut Rng, count: usize) -> String {
+    return (0..count)
+        .map(|_| format!("{:03}A\n", rng.below(1000)))
+        .collect();
+}
+
+// The initial secret numbers of the buyers. They're below 2^24, like the numbers they
+// turn into.
+fn secrets(rng: &mut Rng, buyers: usize) -> String {
+    return (0..buyers)
+        .map(|_| format!("{}\n", rng.between(1, (1 << 24) - 1)))
+        .collect();
+}
+
+const PARTY_SIZE: usize = 13;
+
+// A network of up to 676 computers with two letter names, where each one has up to 13
+// connections like the real network. 13 of them form a LAN party where every computer is
+// connected to the others, and no other computer is connected to all of them.
+fn network(rng: &mut Rng, computers: usize) -> String {
+    return network_with_party(rng, computers).0;
+}
+
+// A network along with the names of the computers at the LAN party.
+fn network_with_party(rng: &mut Rng, computers: usize) -> (String, Vec<String>) {
+    let mut names: Vec<String> = ('a'..='z')
+        .flat_map(|first| ('a'..='z').map(move |second| format!("{first}{second}")))
+        .collect();
+    rng.shuffle(&mut names);
+    names.truncate(computers.clamp(PARTY_SIZE + 1, names.len()));
+
+    // The first `PARTY_SIZE` computers are at the party.
+    let mut connections = vec![];
+    let mut connected = HashSet::new();
+    let mut degrees = vec![0; names.len()];
+    for first in 0..PARTY_SIZE {
+        for second in first + 1..PARTY_SIZE {
+            connections.push((first, second));
+            connected.insert((first, second));
+            degrees[first] += 1;
+            degrees[second] += 1;
+        }
+    }
+
+    // Connect random pairs of computers. A computer outside the party connects to at most
+    // one computer at it, so it can't join the party.
+    let mut knows_party = vec![false; names.len()];
+    for _ in 0..names.len() * 20 {
+        let (first, second) = (rng.below(names.len()), rng.below(names.len()));
+        let (first, second) = (first.min(second), first.max(second));
+        if first == second || degrees[first] == 13 || degrees[second] == 13 {
+            continue;
+        }
+        if connected.contains(&(first, second)) || (first < PARTY_SIZE && knows_party[second]) {
+            continue;
+        }
+
+        knows_party[second] |= first < PARTY_SIZE;
+        connections.push((first, second));
+        connected.insert((first, second));
+        degrees[first] += 1;
+        degrees[second] += 1;
+    }
+
+    let mut lines: Vec<String> = connections
+        .iter()
+        .map(|&(first, second)| match rng.one_in(2) {
+            true => format!("{}-{}\n", names[first], names[second]),
+            false => format!("{}-{}\n", names[second], names[first]),
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+
+    let mut party = names[..PARTY_SIZE].to_vec();
+    party.sort();
+    return (lines.concat(), party);
+}
+
+// A ripple-carry adder of two `bits` bit numbers, like the real circuit, with the outputs
+// of `swaps` pairs of gates swapped. Swaps are only made within a bit and only of the
+// kinds part 2 looks for: a z output with the carry out or with the AND of the carry in,
+// or the XOR of the input bits with their AND.
+pub fn adder(rng: &mut Rng, bits: usize, swaps: usize) -> String {
+    return adder_with_swaps(rng, bits, swaps).0;
+}
+
+struct Gate {
+    input0: String,
+    gate: &'static str,
+    input1: String,
+    output: String,
+}
+
+// A new wire name. Names don't start with x, y or z, which are the inputs and outputs.
+fn new_wire(rng: &mut Rng, used: &mut HashSet<String>) -> String {
+    loop {
+        let first = rng.between(b'a'.into(), b'w'.into()) as u8 as char;
+        let rest: String = (0..2)
+            .map(|_| rng.between(b'a'.into(), b'z'.into()) as u8 as char)
+            .collect();
+        let name = format!("{first}{rest}");
+        if used.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+// An adder along with the sorted names of the swapped wires.
+fn adder_with_swaps(rng: &mut Rng, bits: usize, swaps: usize) -> (String, Vec<String>) {
+    // The output has a bit more than the inputs and has to fit in a u64.
+    let bits = bits.clamp(2, 63);
+    let mut used = HashSet::new();
+    let gate = |input0: &str, gate, input1: &str, output: &str| Gate {
+        input0: input0.to_string(),
+        gate,
+        input1: input1.to_string(),
+        output: output.to_string(),
+    };
+
+    // The input wires come first in the gates of the input bits, which part 2 relies on
+    // to tell the half adder of the lowest bit apart.
+    let mut carry = new_wire(rng, &mut used);
+    let mut gates = vec![
+        gate("x00", "XOR", "y00", "z00"),
+        gate("x00", "AND", "y00", &carry),
+    ];
+    for bit in 1..bits {
+        let (x, y, z) = (
+            format!("x{bit:02}"),
+            format!("y{bit:02}"),
+            format!("z{bit:02}"),
+        );
+        let [sum, generated, propagated] = [(); 3].map(|_| new_wire(rng, &mut used));
+        let carry_out = match bit == bits - 1 {
+            true => format!("z{bits:02}"),
+            false => new_wire(rng, &mut used),
+        };
+
+        gates.push(gate(&x, "XOR", &y, &sum));
+        gates.push(gate(&x, "AND", &y, &generated));
+        gates.push(gate(&sum, "XOR", &carry, &z));
+        gates.push(gate(&sum, "AND", &carry, &propagated));
+        gates.push(gate(&generated, "OR", &propagated, &carry_out));
+        carry = carry_out;
+    }
+
+    // The lowest bit is a half adder and the highest bit's carry out is the last z
+    // output, so neither is swapped.
+    let mut swap_bits: Vec<usize> = (1..bits - 1).collect();
+    rng.shuffle(&mut swap_bits);
+    let mut swapped = vec![];
+    for &bit in swap_bits.iter().take(swaps) {
+        let first_gate = 2 + (bit - 1) * 5;
+        let (first, second) = match rng.below(3) {
+            0 => (first_gate + 2, first_gate + 4),
+            1 => (first_gate + 2, first_gate + 3),
+            _ => (first_gate, first_gate + 1),
+        };
+        let output = gates[first].output.clone();
+        gates[first].output = std::mem::replace(&mut gates[second].output, output);
+        swapped.push(gates[first].output.clone());
+        swapped.push(gates[second].output.clone());
+    }
+    swapped.sort();
+
+    let mut input = String::new();
+    for wire in ['x', 'y'] {
+        for bit in 0..bits {
+            input.push_str(&format!("{wire}{bit:02}: {}\n", rng.below(2)));
+        }
+    }
+    input.push('\n');
+
+    rng.shuffle(&mut gates);
+    for mut gate in gates {
+        if !gate.input0.starts_with('x') && rng.one_in(2) {
+            std::mem::swap(&mut gate.input0, &mut gate.input1);
+        }
+        input.push_str(&format!(
+            "{} {} {} -> {}\n",
+            gate.input0, gate.gate, gate.input1, gate.output
+        ));
+    }
+    return (input, swapped);
+}
+
+// Lock and key schematics with random pin heights, about half of each.
+fn schematics(rng: &mut Rng, count: usize) -> String {
+    let mut schematics = vec![];
+    for _ in 0..count {
+        let heights = [(); 5].map(|_| rng.below(6));
+        let is_lock = rng.one_in(2);
+
+        let mut schematic = String::new();
+        for row in 0..7 {
+            for &height in &heights {
+                // Locks hang from the top row and keys rest on the bottom row.
+                let filled = match is_lock {
+                    true => row <= height,
+                    false => 6 - row <= height,
+                };
+                schematic.push(if filled { '#' } else { '.' });
+            }
+            schematic.push('\n');
+        }
+        schematics.push(schematic);
+    }
+    return schematics.join("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{self, Outcome, Part, Solution};
+    use crate::year2024::*;
+    use std::collections::HashMap;
+
+    const SOLVERS: [fn(&str, Option<Part>) -> Outcome; 25] = [
+        solution::solve::<day01::Solver>,
+        solution::solve::<day02::Solver>,
+        solution::solve::<day03::Solver>,
+        solution::solve::<day04::Solver>,
+        solution::solve::<day05::Solver>,
+        solution::solve::<day06::Solver>,
+        solution::solve::<day07::Solver>,
+        solution::solve::<day08::Solver>,
+        solution::solve::<day09::Solver>,
+        solution::solve::<day10::Solver>,
+        solution::solve::<day11::Solver>,
+        solution::solve::<day12::Solver>,
+        solution::solve::<day13::Solver>,
+        solution::solve::<day14::Solver>,
+        solution::solve::<day15::Solver>,
+        solution::solve::<day16::Solver>,
+        solution::solve::<day17::Solver>,
+        solution::solve::<day18::Solver>,
+        solution::solve::<day19::Solver>,
+        solution::solve::<day20::Solver>,
+        solution::solve::<day21::Solver>,
+        solution::solve::<day22::Solver>,
+        solution::solve::<day23::Solver>,
+        solution::solve::<day24::Solver>,
+        solution::solve::<day25::Solver>,
+    ];
+
+    // Sizes that keep solving every day quick in a debug build.
+    const SMALL_SIZES: [usize; 25] = [
+        50, 50, 50, 20, 20, 15, 20, 12, 30, 12, 3, 20, 20, 500, 10, 21, 16, 1, 10, 21, 5, 10, 40,
+        12, 20,
+    ];
+
+    #[test]
+    fn every_day_solves_its_inputs() {
+        for (index, (generator, solve)) in GENERATORS.iter().zip(SOLVERS).enumerate() {
+            assert_eq!(generator.day as usize, index + 1);
+            // Finding the tree can take thousands of seconds, which is slow without
+            // optimizations. `robots_form_the_tree_once` checks part 2 instead.
+            let part = (generator.day == 14).then_some(Part::One);
+            for seed in 0..2 {
+                let input = generate(generator.day, seed, Some(SMALL_SIZES[index])).unwrap();
+                if let Err(error) = solve(&input, part) {
+                    panic!("Day {} seed {seed}: {error}\n{input}", generator.day);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn seeds_repeat() {
+        assert_eq!(generate(7, 1, Some(10)), generate(7, 1, Some(10)));
+        assert_ne!(generate(7, 1, Some(10)), generate(7, 2, Some(10)));
+        assert_eq!(generate(26, 1, None), None);
+
+        let lines = generate(1, 0, None).unwrap().lines().count();
+        assert_eq!(lines, 1000);
+    }
+
+    #[test]
+    fn rules_order_every_page() {
+        for seed in 0..5 {
+            let input = print_queue(&mut Rng::new(seed), 30);
+            let (rules, updates) = day05::Solver::parse(&input).unwrap();
+
+            // In a total order, a page's place is the number of pages that go before it.
+            let mut place: HashMap<i32, usize> = HashMap::new();
+            for &page in rules.values().flatten() {
+                *place.entry(page).or_default() += 1;
+            }
+            let places: HashSet<usize> = place.values().copied().collect();
+            assert_eq!(places.len(), 48);
+
+            let middles: i32 = updates
+                .iter()
+                .map(|update| {
+                    let mut sorted = update.clone();
+                    sorted.sort_by_key(|page| place.get(page).copied().unwrap_or(0));
+                    return sorted[sorted.len() / 2];
+                })
+                .sum();
+            let input = (rules, updates);
+            assert_eq!(
+                day05::Solver::part1(&input) + day05::Solver::part2(&input),
+                middles
+            );
+        }
+    }
+
+    #[test]
+    fn robots_form_the_tree_once() {
+        for seed in 0..3 {
+            let input = robots_with_tree(&mut Rng::new(seed), 500, 40 + seed as usize);
+            let robots = day14::Solver::parse(&input).unwrap();
+            assert_eq!(day14::Solver::part2(&robots), 40 + seed as isize);
+        }
+    }
+
+    #[test]
+    fn programs_output_themselves() {
+        for seed in 0..20 {
+            let (input, copy) = program_with_copy(&mut Rng::new(seed), 16);
+            let program = day17::Solver::parse(&input).unwrap();
+            assert_eq!(day17::Solver::part2(&program), copy, "{input}");
+        }
+    }
+
+    #[test]
+    fn falling_bytes_block_the_exit() {
+        for seed in 0..3 {
+            let (input, blocker) = falling_bytes_with_blocker(&mut Rng::new(seed), 0);
+            let bytes = day18::Solver::parse(&input).unwrap();
+            assert_eq!(
+                day18::Solver::part2(&bytes),
+                format!("{},{}", blocker.x, blocker.y)
+            );
+        }
+    }
+
+    #[test]
+    fn racetracks_have_a_single_path() {
+        for seed in 0..10 {
+            let track = racetrack(&mut Rng::new(seed), 17, 13);
+            for point in track.points().filter(|&point| track[point] != '#') {
+                let neighbors = track
+                    .neighbors4(point)
+                    .filter(|(_, &tile)| tile != '#')
+                    .count();
+                let expected = if track[point] == '.' { 2 } else { 1 };
+                assert_eq!(neighbors, expected, "{point:?}\n{track}");
+            }
+        }
+    }
+
+    #[test]
+    fn the_lan_party_is_the_largest_group() {
+        for seed in 0..5 {
+            let (input, party) = network_with_party(&mut Rng::new(seed), 60);
+            let network = day23::Solver::parse(&input).unwrap();
+            assert_eq!(day23::Solver::part2(&network), party.join(","));
+        }
+    }
+
+    #[test]
+    fn swapped_wires_are_found() {
+        for seed in 0..20 {
+            let (input, swapped) = adder_with_swaps(&mut Rng::new(seed), 12, 4);
+            let circuit = day24::Solver::parse(&input).unwrap();
+            assert_eq!(swapped.len(), 8);
+            assert_eq!(day24::Solver::part2(&circuit), swapped.join(","), "{input}");
+        }
+
+        // Without swaps, the circuit adds.
+        let input = adder(&mut Rng::new(1), 20, 0);
+        let number = |wire: &str| {
+            return input
+                .lines()
+                .filter(|line| line.starts_with(wire))
+                .enumerate()
+                .map(|(bit, line)| (line.ends_with('1') as u64) << bit)
+                .sum::<u64>();
+        };
+        let circuit = day24::Solver::parse(&input).unwrap();
+        assert_eq!(day24::Solver::part1(&circuit), number("x") + number("y"));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::error::Error;
+use crate::input::fetch::Fetcher;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+// Keeps puzzle inputs in `<root>/yearYYYY/dayNN.txt`. Missing inputs are downloaded with
+// the fetcher, if there is one, and stored with a checksum next to them so a cached input
+// that was changed or truncated afterwards is noticed.
+#[derive(Debug, Clone)]
+pub struct InputCache {
+    root: PathBuf,
+    fetcher: Option<Arc<dyn Fetcher>>,
+}
+
+impl InputCache {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        return InputCache {
+            root: root.into(),
+            fetcher: None,
+        };
+    }
+
+    pub fn with_fetcher(mut self, fetcher: Arc<dyn Fetcher>) -> Self {
+        self.fetcher = Some(fetcher);
+        return self;
+    }
+
+    pub fn path(&self, year: u32, day: u8) -> PathBuf {
+        return self
+            .root
+            .join(format!("year{year}"))
+            .join(format!("day{day:02}.txt"));
+    }
+
+    fn checksum_path(&self, year: u32, day: u8) -> PathBuf {
+        return self
+            .root
+            .join(format!("year{year}"))
+            .join(format!("day{day:02}.txt.checksum"));
+    }
+
+    // Make sure the input of a day is available locally and return its path.
+    pub fn ensure(&self, year: u32, day: u8) -> Result<PathBuf, Error> {
+        let path = self.path(year, day);
+        let checksum_path = self.checksum_path(year, day);
+
+        if path.exists() {
+            // Inputs that were put in place by hand don't have a checksum to verify.
+            if let Ok(expected) = fs::read_to_string(&checksum_path) {
+                if checksum(&fs::read(&path)?) != expected.trim() {
+                    return Err(Error::Checksum { path });
+                }
+            }
+            return Ok(path);
+        }
+
+        let Some(fetcher) = &self.fetcher else {
+            return Err(Error::MissingInput { year, day, path });
+        };
+
+        let input = match fetcher.fetch(year, day) {
+            Ok(input) => input,
+            Err(message) => return Err(Error::Fetch { year, day, message }),
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        write_atomically(&path, input.as_bytes())?;
+        fs::write(&checksum_path, format!("{}\n", checksum(input.as_bytes())))?;
+
+        return Ok(path);
+    }
+}
+
+// Write to a temporary file first so an interrupted download never looks cached.
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), Error> {
+    let temporary = path.with_extension("txt.partial");
+    fs::write(&temporary, contents)?;
+    fs::rename(&temporary, path)?;
+    return Ok(());
+}
+
+// The 64-bit FNV-1a hash of the contents, as hex.
+pub fn checksum(contents: &[u8]) -> String {
+    let hash = contents.iter().fold(0xcbf29ce484222325u64, |hash, &byte| {
+        return (hash ^ byte as u64).wrapping_mul(0x100000001b3);
+    });
+    return format!("{hash:016x}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::fetch::DirectoryFetcher;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // A fresh directory under the system temp dir for each test.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        return dir;
+    }
+
+    #[derive(Debug, Default)]
+    struct CountingFetcher {
+        fetches: AtomicUsize,
+    }
+
+    impl Fetcher for CountingFetcher {
+        fn fetch(&self, year: u32, day: u8) -> Result<String, String> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            return Ok(format!("{year} {day}\n"));
+        }
+    }
+
+    #[test]
+    fn checksum_matches_fnv1a() {
+        assert_eq!(checksum(b""), "cbf29ce484222325");
+        assert_eq!(checksum(b"a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn missing_input_without_fetcher() {
+        let cache = InputCache::new(temp_dir("missing"));
+        let error = cache.ensure(2024, 1).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::MissingInput {
+                year: 2024,
+                day: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn fetches_once_and_caches() {
+        let fetcher = Arc::new(CountingFetcher::default());
+        let cache = InputCache::new(temp_dir("fetch")).with_fetcher(fetcher.clone());
+
+        let path = cache.ensure(2024, 5).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "2024 5\n");
+        cache.ensure(2024, 5).unwrap();
+        assert_eq!(fetcher.fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn detects_modified_inputs() {
+        let cache = InputCache::new(temp_dir("checksum"))
+            .with_fetcher(Arc::new(CountingFetcher::default()));
+
+        let path = cache.ensure(2024, 2).unwrap();
+        fs::write(&path, "tampered\n").unwrap();
+        assert!(matches!(cache.ensure(2024, 2), Err(Error::Checksum { .. })));
+    }
+
+    #[test]
+    fn serves_canned_inputs() {
+        let canned = temp_dir("canned");
+        fs::create_dir_all(canned.join("year2024")).unwrap();
+        fs::write(canned.join("year2024/day03.txt"), "mul(2,4)\n").unwrap();
+
+        let cache = InputCache::new(temp_dir("canned-cache"))
+            .with_fetcher(Arc::new(DirectoryFetcher::new(&canned)));
+        let path = cache.ensure(2024, 3).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "mul(2,4)\n");
+
+        assert!(matches!(
+            cache.ensure(2024, 4),
+            Err(Error::Fetch { day: 4, .. })
+        ));
+    }
+}
@@ -0,0 +1,228 @@
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+// Downloads the input of a puzzle that isn't cached locally yet.
+pub trait Fetcher: fmt::Debug + Send + Sync {
+    fn fetch(&self, year: u32, day: u8) -> Result<String, String>;
+}
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (personal input fetcher)"
+);
+
+// Fetches inputs from the Advent of Code website with the session cookie of a logged in
+// user. Requests are made with `curl` so no HTTP or TLS dependencies are needed.
+#[derive(Clone)]
+pub struct HttpFetcher {
+    base_url: String,
+    session: String,
+}
+
+impl HttpFetcher {
+    pub fn new(session: impl Into<String>) -> Self {
+        return HttpFetcher {
+            base_url: "https://adventofcode.com".to_string(),
+            session: session.into(),
+        };
+    }
+
+    // Use the session cookie from the `AOC_SESSION` environment variable, if it's set.
+    pub fn from_env() -> Option<Self> {
+        return std::env::var("AOC_SESSION")
+            .ok()
+            .map(|session| session.trim().to_string())
+            .filter(|session| !session.is_empty())
+            .map(HttpFetcher::new);
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        return self;
+    }
+
+    pub fn url(&self, year: u32, day: u8) -> String {
+        return format!(
+            "{}/{year}/day/{day}/input",
+            self.base_url.trim_end_matches('/')
+        );
+    }
+}
+
+// Keep the session cookie out of debug output.
+impl fmt::Debug for HttpFetcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f
+            .debug_struct("HttpFetcher")
+            .field("base_url", &self.base_url)
+            .field("session", &"<redacted>")
+            .finish();
+    }
+}
+
+// Whether the session can be written into the curl config as is. Session cookies are
+// hex strings, and a quote, backslash or newline would change the config.
+fn is_plain_token(session: &str) -> bool {
+    return session
+        .chars()
+        .all(|char| char.is_ascii_alphanumeric() || matches!(char, '-' | '_' | '.'));
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, year: u32, day: u8) -> Result<String, String> {
+        if !is_plain_token(&self.session) {
+            return Err(
+                "the session must only contain letters, digits, '-', '_' and '.'".to_string(),
+            );
+        }
+
+        let mut curl = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| format!("failed to run curl: {error}"))?;
+
+        // The cookie is passed through a config on stdin so it doesn't show up in the
+        // process list.
+        let config = format!(
+            "url = \"{}\"\ncookie = \"session={}\"\nuser-agent = \"{USER_AGENT}\"\n",
+            self.url(year, day),
+            self.session
+        );
+        curl.stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(config.as_bytes())
+            .map_err(|error| format!("failed to configure curl: {error}"))?;
+
+        let output = curl
+            .wait_with_output()
+            .map_err(|error| format!("failed to run curl: {error}"))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+
+        return String::from_utf8(output.stdout)
+            .map_err(|_| "the downloaded input isn't valid UTF-8".to_string());
+    }
+}
+
+// Serves canned inputs from `<dir>/yearYYYY/dayNN.txt`, standing in for the website when
+// working offline or in tests.
+#[derive(Debug, Clone)]
+pub struct DirectoryFetcher {
+    dir: PathBuf,
+}
+
+impl DirectoryFetcher {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        return DirectoryFetcher { dir: dir.into() };
+    }
+}
+
+impl Fetcher for DirectoryFetcher {
+    fn fetch(&self, year: u32, day: u8) -> Result<String, String> {
+        let path = self
+            .dir
+            .join(format!("year{year}"))
+            .join(format!("day{day:02}.txt"));
+        return fs::read_to_string(&path).map_err(|error| format!("{}: {error}", path.display()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // The HTTP tests run the real `curl`, so they're skipped where it isn't installed.
+    fn curl_installed() -> bool {
+        return Command::new("curl")
+            .arg("--version")
+            .stdout(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+    }
+
+    // Serve a single canned response over plain HTTP on a local port, standing in for
+    // the website. Returns the base URL and the request headers that were received.
+    fn serve_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let headers: Vec<String> = BufReader::new(stream.try_clone().unwrap())
+                .lines()
+                .map_while(Result::ok)
+                .take_while(|line| !line.is_empty())
+                .collect();
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            return headers;
+        });
+
+        return (base_url, server);
+    }
+
+    #[test]
+    fn http_fetcher_sends_the_session_cookie() {
+        if !curl_installed() {
+            return;
+        }
+        let (base_url, server) = serve_once("200 OK", "1 2\n3 4\n");
+        let fetcher = HttpFetcher::new("secret").with_base_url(base_url);
+
+        assert_eq!(fetcher.fetch(2024, 1).unwrap(), "1 2\n3 4\n");
+
+        let headers = server.join().unwrap();
+        assert_eq!(headers[0], "GET /2024/day/1/input HTTP/1.1");
+        assert!(headers
+            .iter()
+            .any(|header| header == "Cookie: session=secret"));
+    }
+
+    #[test]
+    fn http_fetcher_reports_failed_requests() {
+        if !curl_installed() {
+            return;
+        }
+        let (base_url, server) = serve_once("400 Bad Request", "Please log in.");
+        let fetcher = HttpFetcher::new("expired").with_base_url(base_url);
+
+        assert!(fetcher.fetch(2024, 1).unwrap_err().contains("400"));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn http_fetcher_rejects_sessions_that_would_change_the_config() {
+        for session in ["abc\"\nurl = \"file:///etc/passwd", "abc\\", "a b"] {
+            let error = HttpFetcher::new(session).fetch(2024, 1).unwrap_err();
+            assert!(
+                error.starts_with("the session must only contain"),
+                "{error}"
+            );
+        }
+    }
+
+    #[test]
+    fn debug_output_hides_the_session() {
+        let fetcher = HttpFetcher::new("secret");
+        assert!(!format!("{fetcher:?}").contains("secret"));
+    }
+}
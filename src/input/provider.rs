@@ -0,0 +1,168 @@
+use crate::error::Error;
+use crate::input::cache::InputCache;
+use crate::input::embedded;
+use crate::input::fetch::Fetcher;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    Text(String),
+    // An input built into the binary with the `embed-inputs` feature.
+    Embedded {
+        year: u32,
+        day: u8,
+        text: &'static str,
+    },
+}
+
+impl Source {
+    // Read the whole input into memory at once. Parsers then borrow their lines and
+    // bytes from it instead of allocating a string per line. Embedded inputs are already
+    // in memory, so they're borrowed rather than copied.
+    pub fn read(&self) -> io::Result<Cow<'static, str>> {
+        return match self {
+            Source::File(path) => fs::read_to_string(path).map(Cow::Owned),
+            Source::Stdin => io::read_to_string(io::stdin().lock()).map(Cow::Owned),
+            Source::Text(text) => Ok(Cow::Owned(text.clone())),
+            Source::Embedded { text, .. } => Ok(Cow::Borrowed(text)),
+        };
+    }
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::Text(_) => write!(f, "<text>"),
+            Source::Embedded { year, day, .. } => {
+                write!(f, "<embedded year{year}/day{day:02}.txt>")
+            }
+        };
+    }
+}
+
+// Resolves the input of a puzzle by year and day.
+// By default inputs are read from `<root>/yearYYYY/dayNN.txt`, and downloaded there first
+// when a fetcher is set, but any day can be overridden to read from another file, stdin
+// or an in-memory string. Inputs built into the binary are used before the files when
+// `with_embedded` is set.
+#[derive(Debug, Clone)]
+pub struct InputProvider {
+    cache: InputCache,
+    overrides: HashMap<(u32, u8), Source>,
+    embedded: bool,
+}
+
+impl Default for InputProvider {
+    fn default() -> Self {
+        return InputProvider::new("input");
+    }
+}
+
+impl InputProvider {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        return InputProvider {
+            cache: InputCache::new(root),
+            overrides: HashMap::new(),
+            embedded: false,
+        };
+    }
+
+    pub fn with_fetcher(mut self, fetcher: Arc<dyn Fetcher>) -> Self {
+        self.cache = self.cache.with_fetcher(fetcher);
+        return self;
+    }
+
+    // Prefer the inputs built into the binary, if it was built with the `embed-inputs`
+    // feature. Days without one are still read from the files.
+    pub fn with_embedded(mut self) -> Self {
+        self.embedded = true;
+        return self;
+    }
+
+    fn embedded(&self, year: u32, day: u8) -> Option<Source> {
+        if !self.embedded {
+            return None;
+        }
+        return embedded::get(year, day).map(|text| Source::Embedded { year, day, text });
+    }
+
+    pub fn with_override(mut self, year: u32, day: u8, source: Source) -> Self {
+        self.overrides.insert((year, day), source);
+        return self;
+    }
+
+    pub fn path(&self, year: u32, day: u8) -> PathBuf {
+        return self.cache.path(year, day);
+    }
+
+    // Where the input of a day will be read from, without fetching it.
+    pub fn source(&self, year: u32, day: u8) -> Source {
+        if let Some(source) = self.overrides.get(&(year, day)) {
+            return source.clone();
+        }
+        return self
+            .embedded(year, day)
+            .unwrap_or_else(|| Source::File(self.path(year, day)));
+    }
+
+    // Like `source`, but makes sure a file input is available, fetching it if needed.
+    pub fn resolve(&self, year: u32, day: u8) -> Result<Source, Error> {
+        if let Some(source) = self.overrides.get(&(year, day)) {
+            return Ok(source.clone());
+        }
+        if let Some(source) = self.embedded(year, day) {
+            return Ok(source);
+        }
+        return Ok(Source::File(self.cache.ensure(year, day)?));
+    }
+
+    // The whole input of a day.
+    pub fn read(&self, year: u32, day: u8) -> Result<Cow<'static, str>, Error> {
+        return Ok(self.resolve(year, day)?.read()?);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_come_first() {
+        let provider = InputProvider::new("inputs").with_embedded().with_override(
+            2024,
+            1,
+            Source::Text("1 2\n".to_string()),
+        );
+
+        assert_eq!(provider.source(2024, 1), Source::Text("1 2\n".to_string()));
+        assert_eq!(provider.read(2024, 1).unwrap(), "1 2\n");
+    }
+
+    #[test]
+    fn days_without_an_embedded_input_read_files() {
+        let provider = InputProvider::new("inputs").with_embedded();
+        assert_eq!(
+            provider.source(1999, 1),
+            Source::File(PathBuf::from("inputs/year1999/day01.txt"))
+        );
+    }
+
+    #[test]
+    fn embedded_inputs_are_borrowed() {
+        let source = Source::Embedded {
+            year: 2024,
+            day: 1,
+            text: "1 2\n",
+        };
+        assert!(matches!(source.read().unwrap(), Cow::Borrowed("1 2\n")));
+    }
+}
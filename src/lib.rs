@@ -1,7 +1,38 @@
+pub mod error;
+
+pub mod generate {
+    pub mod year2024;
+}
+
+pub mod input {
+    pub mod cache;
+    pub mod embedded;
+    pub mod fetch;
+    pub mod provider;
+}
+
+pub mod runner {
+    pub mod answers;
+    pub mod bench;
+    pub mod cli;
+    pub mod pool;
+    pub mod puzzle;
+    pub mod scaffold;
+}
+
+pub mod solution;
+
 pub mod util {
+    pub mod direction;
     pub mod file;
     pub mod grid;
+    pub mod image;
     pub mod point;
+    pub mod random;
+    pub mod region;
+    pub mod search;
+    pub mod sparse;
+    pub mod window;
 }
 
 pub mod year2024 {
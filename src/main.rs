@@ -1,37 +1,41 @@
+use adventofcode::runner::{cli, puzzle::Puzzle};
 use adventofcode::*;
+use std::process::ExitCode;
 
-macro_rules! run_test {
-    ($year:tt, $day:tt) => {{
-        use $year::$day::*;
-
-        run();
-    }};
+macro_rules! puzzle {
+    ($year:tt, $day:tt) => {
+        Puzzle::new::<$year::$day::Solver>(stringify!($year), stringify!($day))
+    };
 }
 
-fn main() {
-    // run_test!(year2024, day01);
-    // run_test!(year2024, day02);
-    // run_test!(year2024, day03);
-    // run_test!(year2024, day04);
-    // run_test!(year2024, day05);
-    // run_test!(year2024, day06);
-    // run_test!(year2024, day07);
-    // run_test!(year2024, day08);
-    // run_test!(year2024, day09);
-    // run_test!(year2024, day10);
-    // run_test!(year2024, day11);
-    // run_test!(year2024, day12);
-    // run_test!(year2024, day13);
-    // run_test!(year2024, day14);
-    // run_test!(year2024, day15);
-    // run_test!(year2024, day16);
-    // run_test!(year2024, day17);
-    // run_test!(year2024, day18);
-    // run_test!(year2024, day19);
-    // run_test!(year2024, day20);
-    // run_test!(year2024, day21);
-    // run_test!(year2024, day22);
-    // run_test!(year2024, day23);
-    // run_test!(year2024, day24);
-    run_test!(year2024, day25);
+fn main() -> ExitCode {
+    let puzzles = [
+        puzzle!(year2024, day01),
+        puzzle!(year2024, day02),
+        puzzle!(year2024, day03),
+        puzzle!(year2024, day04),
+        puzzle!(year2024, day05),
+        puzzle!(year2024, day06),
+        puzzle!(year2024, day07),
+        puzzle!(year2024, day08),
+        puzzle!(year2024, day09),
+        puzzle!(year2024, day10),
+        puzzle!(year2024, day11),
+        puzzle!(year2024, day12),
+        puzzle!(year2024, day13),
+        puzzle!(year2024, day14),
+        puzzle!(year2024, day15),
+        puzzle!(year2024, day16),
+        puzzle!(year2024, day17),
+        puzzle!(year2024, day18),
+        puzzle!(year2024, day19),
+        puzzle!(year2024, day20),
+        puzzle!(year2024, day21),
+        puzzle!(year2024, day22),
+        puzzle!(year2024, day23),
+        puzzle!(year2024, day24),
+        puzzle!(year2024, day25),
+    ];
+
+    return cli::main(&puzzles);
 }
@@ -0,0 +1,362 @@
+use crate::solution::Part;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+// The outcome of comparing a computed answer against the store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+// Answers recorded for a single day, indexed by part.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct DayAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl DayAnswers {
+    fn get_mut(&mut self, part: Part) -> &mut Option<String> {
+        return match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        };
+    }
+}
+
+// Known good answers, stored as one TOML file per year such as `answers/2024.toml`:
+//
+// [day01]
+// part1 = "11"
+// part2 = "31"
+//
+// Only the subset of TOML needed for this layout is supported. Answers are TOML basic
+// strings, so escapes such as `\"` and `\n` are read and written the same way. Comments
+// start with `#`, on their own line or after a value.
+#[derive(Debug, Clone)]
+pub struct AnswerStore {
+    dir: PathBuf,
+    years: BTreeMap<u32, BTreeMap<u8, DayAnswers>>,
+}
+
+impl AnswerStore {
+    // Create an empty store that saves into `dir`.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        return AnswerStore {
+            dir: dir.into(),
+            years: BTreeMap::new(),
+        };
+    }
+
+    // Load every `<year>.toml` file in `dir`. A missing directory is an empty store.
+    pub fn load<P: Into<PathBuf>>(dir: P) -> io::Result<Self> {
+        let mut store = AnswerStore::new(dir);
+
+        let entries = match fs::read_dir(&store.dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(store),
+            Err(error) => return Err(error),
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+            let year = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u32>().ok());
+
+            if let (Some(year), Some("toml")) =
+                (year, path.extension().and_then(|ext| ext.to_str()))
+            {
+                let days = parse_year_file(&path)?;
+                store.years.insert(year, days);
+            }
+        }
+
+        return Ok(store);
+    }
+
+    pub fn expected(&self, year: u32, day: u8, part: Part) -> Option<&str> {
+        let answers = self.years.get(&year)?.get(&day)?;
+        let answer = match part {
+            Part::One => &answers.part1,
+            Part::Two => &answers.part2,
+        };
+        return answer.as_deref();
+    }
+
+    pub fn check(&self, year: u32, day: u8, part: Part, answer: &str) -> Verdict {
+        return match self.expected(year, day, part) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        };
+    }
+
+    // Record an answer for a part that doesn't have one yet. A known answer is never
+    // replaced, since the new one may be wrong. Returns whether the answer was recorded.
+    pub fn record(&mut self, year: u32, day: u8, part: Part, answer: &str) -> bool {
+        let known = self
+            .years
+            .entry(year)
+            .or_default()
+            .entry(day)
+            .or_default()
+            .get_mut(part);
+        if known.is_some() {
+            return false;
+        }
+        *known = Some(answer.to_string());
+        return true;
+    }
+
+    pub fn path(&self, year: u32) -> PathBuf {
+        return self.dir.join(format!("{year}.toml"));
+    }
+
+    // Write every year to its TOML file. Each file is rewritten from the answers in the
+    // store, so comments and blank lines in it aren't kept.
+    pub fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        for (&year, days) in &self.years {
+            let mut contents = String::new();
+            for (day, answers) in days {
+                if !contents.is_empty() {
+                    contents.push('\n');
+                }
+                contents.push_str(&format!("[day{day:02}]\n"));
+                for (key, answer) in [("part1", &answers.part1), ("part2", &answers.part2)] {
+                    if let Some(answer) = answer {
+                        contents.push_str(&format!("{key} = {}\n", quote(answer)));
+                    }
+                }
+            }
+            fs::write(self.path(year), contents)?;
+        }
+
+        return Ok(());
+    }
+}
+
+// Write `value` as a TOML basic string. Control characters without a short escape
+// are written as `\uXXXX`.
+fn quote(value: &str) -> String {
+    let mut output = String::from('"');
+    for char in value.chars() {
+        match char {
+            '\\' => output.push_str("\\\\"),
+            '"' => output.push_str("\\\""),
+            '\n' => output.push_str("\\n"),
+            '\t' => output.push_str("\\t"),
+            '\r' => output.push_str("\\r"),
+            char if char.is_control() => output.push_str(&format!("\\u{:04X}", char as u32)),
+            char => output.push(char),
+        }
+    }
+    output.push('"');
+    return output;
+}
+
+// Read a TOML basic string, undoing the escapes `quote` writes. `None` when the value
+// isn't quoted or has an escape TOML doesn't define.
+fn unquote(value: &str) -> Option<String> {
+    let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut output = String::new();
+    let mut chars = inner.chars();
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => {
+                let escaped = match chars.next()? {
+                    '\\' => '\\',
+                    '"' => '"',
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    'b' => '\u{8}',
+                    'f' => '\u{c}',
+                    'u' => unicode_escape(&mut chars, 4)?,
+                    'U' => unicode_escape(&mut chars, 8)?,
+                    _ => return None,
+                };
+                output.push(escaped);
+            }
+            '"' => return None,
+            _ => output.push(char),
+        }
+    }
+    return Some(output);
+}
+
+// The character named by the next `digits` hex digits of a `\u` or `\U` escape.
+fn unicode_escape(chars: &mut std::str::Chars, digits: usize) -> Option<char> {
+    let hex: String = chars.take(digits).collect();
+    if hex.len() != digits {
+        return None;
+    }
+    return char::from_u32(u32::from_str_radix(&hex, 16).ok()?);
+}
+
+// The line without its comment. A `#` inside a quoted answer doesn't start a comment.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut chars = line.char_indices();
+    while let Some((index, char)) = chars.next() {
+        match char {
+            '"' => in_string = !in_string,
+            '\\' if in_string => {
+                chars.next();
+            }
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+    return line;
+}
+
+fn parse_year_file(path: &Path) -> io::Result<BTreeMap<u8, DayAnswers>> {
+    let contents = fs::read_to_string(path)?;
+    let mut days: BTreeMap<u8, DayAnswers> = BTreeMap::new();
+    let mut current_day = None;
+
+    for (index, line) in contents.lines().enumerate() {
+        let invalid = |message: &str| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!("{}:{}: {message}", path.display(), index + 1),
+            )
+        };
+
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(section) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            let day = section
+                .strip_prefix("day")
+                .and_then(|day| day.parse::<u8>().ok())
+                .ok_or_else(|| invalid("expected a section like [day01]"))?;
+            days.entry(day).or_default();
+            current_day = Some(day);
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| invalid("expected `part1 = \"...\"`"))?;
+        let part = match key.trim() {
+            "part1" => Part::One,
+            "part2" => Part::Two,
+            key => return Err(invalid(&format!("unknown key '{key}'"))),
+        };
+        let value = unquote(value.trim()).ok_or_else(|| invalid("expected a quoted string"))?;
+        let day = current_day.ok_or_else(|| invalid("answer outside of a [dayNN] section"))?;
+
+        *days.entry(day).or_default().get_mut(part) = Some(value);
+    }
+
+    return Ok(days);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A fresh directory under the system temp dir for each test.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-answers-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        return dir;
+    }
+
+    fn store_with(name: &str, contents: &str) -> AnswerStore {
+        let dir = temp_dir(name);
+        fs::write(dir.join("2024.toml"), contents).unwrap();
+        return AnswerStore::load(dir).unwrap();
+    }
+
+    #[test]
+    fn check_compares_with_the_known_answer() {
+        let store = store_with("check", "[day01]\npart1 = \"11\"\n");
+        assert_eq!(store.check(2024, 1, Part::One, "11"), Verdict::Pass);
+        assert_eq!(
+            store.check(2024, 1, Part::One, "12"),
+            Verdict::Fail {
+                expected: "11".to_string()
+            }
+        );
+        assert_eq!(store.check(2024, 1, Part::Two, "31"), Verdict::Unknown);
+        assert_eq!(store.check(2024, 2, Part::One, "11"), Verdict::Unknown);
+        assert_eq!(store.check(2023, 1, Part::One, "11"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn record_keeps_known_answers() {
+        let mut store = store_with("record", "[day01]\npart1 = \"11\"\n");
+        assert!(!store.record(2024, 1, Part::One, "12"));
+        assert!(store.record(2024, 1, Part::Two, "31"));
+        store.save().unwrap();
+
+        let saved = fs::read_to_string(store.path(2024)).unwrap();
+        assert_eq!(saved, "[day01]\npart1 = \"11\"\npart2 = \"31\"\n");
+    }
+
+    #[test]
+    fn load_treats_a_missing_directory_as_empty() {
+        let dir = temp_dir("missing").join("answers");
+        let store = AnswerStore::load(&dir).unwrap();
+        assert_eq!(store.check(2024, 1, Part::One, "11"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn reads_comments_after_values() {
+        let store = store_with(
+            "comments",
+            "# Answers\n[day01] # first day\npart1 = \"1522137\" # ok\npart2 = \"a # b\"#no space\n",
+        );
+        assert_eq!(store.expected(2024, 1, Part::One), Some("1522137"));
+        assert_eq!(store.expected(2024, 1, Part::Two), Some("a # b"));
+        assert_eq!(
+            strip_comment(r##"part1 = "\"#" # x"##),
+            r##"part1 = "\"#" "##
+        );
+    }
+
+    #[test]
+    fn quoted_answers_round_trip() {
+        for answer in [
+            "11",
+            "a \"quoted\" \\ path",
+            "two\nlines\tand a tab",
+            "bell \u{7}",
+        ] {
+            assert_eq!(unquote(&quote(answer)).as_deref(), Some(answer));
+        }
+        assert_eq!(quote("a\nb"), "\"a\\nb\"");
+        assert_eq!(quote("\u{7}"), "\"\\u0007\"");
+    }
+
+    #[test]
+    fn reads_toml_escapes() {
+        assert_eq!(
+            unquote(r#""\u00e9\U0001F384""#).as_deref(),
+            Some("\u{e9}\u{1F384}")
+        );
+        assert_eq!(unquote(r#""a\"b""#).as_deref(), Some("a\"b"));
+        assert_eq!(unquote(r#""\x""#), None);
+        assert_eq!(unquote(r#""\u12""#), None);
+        assert_eq!(unquote(r#""a"b""#), None);
+    }
+}
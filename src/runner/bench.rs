@@ -0,0 +1,280 @@
+use crate::runner::puzzle::{Failure, Puzzle};
+use crate::solution::{Part, Timings};
+use std::cmp::Reverse;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    // Summarize the samples. There must be at least one sample.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        return Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Benchmark {
+    pub year: u32,
+    pub day: u8,
+    pub parse: Stats,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+    pub total: Stats,
+}
+
+// Solve a puzzle `iterations` times. The input is read from memory so that file
+// access isn't part of the parse time. Like `Puzzle::run`, a panic is returned as a
+// failure instead of aborting the other benchmarks.
+pub fn run(
+    puzzle: &Puzzle,
+    input: &str,
+    source: &str,
+    part: Option<Part>,
+    iterations: usize,
+) -> Result<Benchmark, Failure> {
+    let samples: Vec<Timings> = (0..iterations.max(1))
+        .map(|_| {
+            return puzzle
+                .solve_input(input, source, part)
+                .map(|(_, timings)| timings);
+        })
+        .collect::<Result<_, _>>()?;
+
+    let stats = |select: fn(&Timings) -> Option<Duration>| {
+        let durations: Option<Vec<Duration>> = samples.iter().map(select).collect();
+        return durations.map(Stats::from_samples);
+    };
+
+    return Ok(Benchmark {
+        year: puzzle.year,
+        day: puzzle.day,
+        parse: stats(|timings| Some(timings.parse)).expect("Parsing is always timed"),
+        part1: stats(|timings| timings.part1),
+        part2: stats(|timings| timings.part2),
+        total: stats(|timings| Some(timings.total())).expect("The total is always timed"),
+    });
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64();
+
+    return if seconds >= 1.0 {
+        format!("{seconds:.3}s")
+    } else if seconds >= 1e-3 {
+        format!("{:.3}ms", seconds * 1e3)
+    } else {
+        format!("{:.1}µs", seconds * 1e6)
+    };
+}
+
+// Sort the benchmarks so the slowest days come first.
+pub fn sort(benchmarks: &mut [Benchmark]) {
+    benchmarks.sort_by_key(|benchmark| Reverse(benchmark.total.median));
+}
+
+// A plain text table with the median of each step and the spread of the total.
+pub fn table(benchmarks: &[Benchmark]) -> String {
+    let optional = |stats: Option<Stats>| match stats {
+        Some(stats) => format_duration(stats.median),
+        None => "-".to_string(),
+    };
+
+    let mut rows = vec![[
+        "Day".to_string(),
+        "Parse".to_string(),
+        "Part 1".to_string(),
+        "Part 2".to_string(),
+        "Min".to_string(),
+        "Median".to_string(),
+        "Max".to_string(),
+    ]];
+    for benchmark in benchmarks {
+        rows.push([
+            format!("{} day {:02}", benchmark.year, benchmark.day),
+            format_duration(benchmark.parse.median),
+            optional(benchmark.part1),
+            optional(benchmark.part2),
+            format_duration(benchmark.total.min),
+            format_duration(benchmark.total.median),
+            format_duration(benchmark.total.max),
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut output = String::new();
+    for (index, row) in rows.iter().enumerate() {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(col, (cell, &width))| match col {
+                0 => format!("{cell:<width$}"),
+                _ => format!("{cell:>width$}"),
+            })
+            .collect::<Vec<String>>()
+            .join(" | ");
+        output.push_str(line.trim_end());
+        output.push('\n');
+
+        // Underline the header.
+        if index == 0 {
+            let separator = widths
+                .iter()
+                .map(|&width| "-".repeat(width))
+                .collect::<Vec<String>>()
+                .join("-|-");
+            output.push_str(&separator);
+            output.push('\n');
+        }
+    }
+
+    return output;
+}
+
+fn stats_json(stats: Option<Stats>) -> String {
+    return match stats {
+        Some(stats) => format!(
+            "{{\"min_ns\": {}, \"median_ns\": {}, \"max_ns\": {}}}",
+            stats.min.as_nanos(),
+            stats.median.as_nanos(),
+            stats.max.as_nanos()
+        ),
+        None => "null".to_string(),
+    };
+}
+
+// A JSON report that can be saved and compared between runs.
+pub fn to_json(benchmarks: &[Benchmark], iterations: usize) -> String {
+    let results = benchmarks
+        .iter()
+        .map(|benchmark| {
+            format!(
+                "    {{\"year\": {}, \"day\": {}, \"parse\": {}, \"part1\": {}, \"part2\": {}, \"total\": {}}}",
+                benchmark.year,
+                benchmark.day,
+                stats_json(Some(benchmark.parse)),
+                stats_json(benchmark.part1),
+                stats_json(benchmark.part2),
+                stats_json(Some(benchmark.total)),
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",\n");
+    let results = if results.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{results}\n  ]")
+    };
+
+    return format!("{{\n  \"iterations\": {iterations},\n  \"results\": {results}\n}}\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        return values
+            .iter()
+            .map(|&value| Duration::from_millis(value))
+            .collect();
+    }
+
+    fn stats(min: u64, median: u64, max: u64) -> Stats {
+        return Stats {
+            min: Duration::from_millis(min),
+            median: Duration::from_millis(median),
+            max: Duration::from_millis(max),
+        };
+    }
+
+    fn benchmark(day: u8, total: Stats) -> Benchmark {
+        return Benchmark {
+            year: 2024,
+            day,
+            parse: stats(1, 1, 1),
+            part1: Some(stats(2, 3, 4)),
+            part2: None,
+            total,
+        };
+    }
+
+    #[test]
+    fn stats_from_an_odd_number_of_samples() {
+        assert_eq!(Stats::from_samples(millis(&[9, 1, 4])), stats(1, 4, 9));
+    }
+
+    #[test]
+    fn stats_from_an_even_number_of_samples() {
+        // The median is halfway between the two middle samples.
+        assert_eq!(Stats::from_samples(millis(&[8, 2, 1, 4])), stats(1, 3, 8));
+    }
+
+    #[test]
+    fn stats_from_a_single_sample() {
+        assert_eq!(Stats::from_samples(millis(&[5])), stats(5, 5, 5));
+    }
+
+    #[test]
+    fn sort_puts_the_slowest_median_first() {
+        let mut benchmarks = vec![
+            benchmark(1, stats(1, 5, 50)),
+            benchmark(2, stats(20, 30, 40)),
+            benchmark(3, stats(9, 10, 11)),
+        ];
+        sort(&mut benchmarks);
+
+        let days: Vec<u8> = benchmarks.iter().map(|benchmark| benchmark.day).collect();
+        assert_eq!(days, [2, 3, 1]);
+    }
+
+    #[test]
+    fn json_shape() {
+        // Every value is a number or null, so nothing needs escaping.
+        let json = to_json(&[benchmark(7, stats(5, 6, 7))], 10);
+        assert_eq!(
+            json,
+            r#"{
+  "iterations": 10,
+  "results": [
+    {"year": 2024, "day": 7, "parse": {"min_ns": 1000000, "median_ns": 1000000, "max_ns": 1000000}, "part1": {"min_ns": 2000000, "median_ns": 3000000, "max_ns": 4000000}, "part2": null, "total": {"min_ns": 5000000, "median_ns": 6000000, "max_ns": 7000000}}
+  ]
+}
+"#
+        );
+    }
+
+    #[test]
+    fn json_without_results() {
+        assert_eq!(
+            to_json(&[], 3),
+            "{\n  \"iterations\": 3,\n  \"results\": []\n}\n"
+        );
+    }
+}
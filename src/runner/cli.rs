@@ -0,0 +1,747 @@
+use crate::error::Error;
+use crate::generate::year2024;
+use crate::input::fetch::HttpFetcher;
+use crate::input::provider::{InputProvider, Source};
+use crate::runner::answers::{AnswerStore, Verdict};
+use crate::runner::bench::{self, format_duration};
+use crate::runner::pool;
+use crate::runner::puzzle::{self, Failure, Puzzle};
+use crate::runner::scaffold;
+use crate::solution::Part;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+const USAGE: &str = "\
+Usage: adventofcode <all | YEAR [DAYS]> [OPTIONS]
+       adventofcode scaffold <YEAR> <DAY> <TITLE>
+       adventofcode generate <YEAR> <DAY> [--seed <N>] [--size <N>]
+
+  all           Run every registered day of every year.
+  YEAR          Run every registered day of YEAR, e.g. `2024`.
+  DAYS          A day, range or comma separated list, e.g. `16`, `1-5` or `1,3,10-12`.
+                `all` selects every day of YEAR.
+  scaffold      Create src/yearYYYY/dayNN.rs from a template and register the day in
+                lib.rs, main.rs and the README table with the puzzle TITLE.
+  generate      Print a random input for a day, made from the seed (0 by default). The same
+                seed always makes the same input. --size sets how big it is, in a unit that
+                depends on the day, and is about the size of a real input by default.
+
+Options:
+  -p, --part <1|2>         Only run the given part.
+  -i, --input <FILE>       Read the input of the selected day from FILE, or stdin when FILE is `-`.
+                           Only valid when a single day is selected.
+      --input-dir <DIR>    Read inputs from DIR/yearYYYY/dayNN.txt instead of input/yearYYYY/dayNN.txt.
+      --offline            Don't download missing inputs.
+      --answers-dir <DIR>  Check answers against DIR/YEAR.toml instead of answers/YEAR.toml.
+      --record             Save answers that aren't in the answers file yet.
+  -t, --time               Show how long parsing and each part took.
+      --bench <N>          Solve each day N times and print a table of timings, slowest first.
+                           Answers aren't checked and days are solved one at a time.
+      --json <FILE>        With --bench, also write the timings to FILE as JSON.
+  -j, --jobs <N>           Solve up to N days at the same time.
+      --parallel           Solve days on every available core. Implies --time.
+  -h, --help               Print this message.
+
+Computed answers are checked against the answers file and reported as pass, FAIL or
+unknown. The exit code is non-zero when any answer doesn't match. Answers aren't checked
+when --input is used.
+
+Missing inputs are downloaded into the input directory when the AOC_SESSION environment
+variable holds the session cookie of your Advent of Code login.";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaySelection {
+    All,
+    Days(Vec<RangeInclusive<u8>>),
+}
+
+impl DaySelection {
+    pub fn contains(&self, day: u8) -> bool {
+        return match self {
+            DaySelection::All => true,
+            DaySelection::Days(ranges) => ranges.iter().any(|range| range.contains(&day)),
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    // `None` means every year.
+    pub year: Option<u32>,
+    pub days: DaySelection,
+    pub part: Option<Part>,
+    pub input: Option<Source>,
+    pub input_dir: Option<PathBuf>,
+    pub offline: bool,
+    pub answers_dir: Option<PathBuf>,
+    pub record: bool,
+    pub time: bool,
+    pub bench: Option<usize>,
+    pub json: Option<PathBuf>,
+    // How many days to solve at the same time.
+    pub jobs: usize,
+}
+
+impl Args {
+    pub fn selects(&self, puzzle: &Puzzle) -> bool {
+        return self.year.is_none_or(|year| year == puzzle.year) && self.days.contains(puzzle.day);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Run(Args),
+    Scaffold {
+        year: u32,
+        day: u8,
+        title: String,
+    },
+    Generate {
+        year: u32,
+        day: u8,
+        seed: u64,
+        size: Option<usize>,
+    },
+    Help,
+}
+
+fn parse_day(input: &str) -> Result<u8, String> {
+    return match input.parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!(
+            "Invalid day '{input}', expected a number from 1 to 25"
+        )),
+    };
+}
+
+fn parse_days(input: &str) -> Result<DaySelection, String> {
+    if input == "all" {
+        return Ok(DaySelection::All);
+    }
+
+    let ranges = input
+        .split(',')
+        .map(|range| match range.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    return Err(format!("Invalid day range '{range}'"));
+                }
+                Ok(start..=end)
+            }
+            None => parse_day(range).map(|day| day..=day),
+        })
+        .collect::<Result<Vec<RangeInclusive<u8>>, String>>()?;
+
+    return Ok(DaySelection::Days(ranges));
+}
+
+fn parse_input(input: Option<String>) -> Result<Source, String> {
+    return match input.as_deref() {
+        Some("-") => Ok(Source::Stdin),
+        Some(path) => Ok(Source::File(PathBuf::from(path))),
+        None => Err("--input requires a value".to_string()),
+    };
+}
+
+fn parse_part(input: Option<String>) -> Result<Part, String> {
+    return match input.as_deref() {
+        Some("1") => Ok(Part::One),
+        Some("2") => Ok(Part::Two),
+        Some(part) => Err(format!("Invalid part '{part}', expected 1 or 2")),
+        None => Err("--part requires a value".to_string()),
+    };
+}
+
+// Parse the command line arguments, excluding the program name.
+pub fn parse<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter().peekable();
+    if args.next_if(|arg| arg == "scaffold").is_some() {
+        return parse_scaffold(args);
+    }
+    if args.next_if(|arg| arg == "generate").is_some() {
+        return parse_generate(args);
+    }
+
+    let mut positional = vec![];
+    let mut part = None;
+    let mut input = None;
+    let mut input_dir = None;
+    let mut offline = false;
+    let mut answers_dir = None;
+    let mut record = false;
+    let mut time = false;
+    let mut bench = None;
+    let mut json = None;
+    let mut jobs = 1;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--part" | "-p" => part = Some(parse_part(args.next())?),
+            "--input" | "-i" => input = Some(parse_input(args.next())?),
+            "--input-dir" => {
+                let dir = args.next().ok_or("--input-dir requires a value")?;
+                input_dir = Some(PathBuf::from(dir));
+            }
+            "--offline" => offline = true,
+            "--answers-dir" => {
+                let dir = args.next().ok_or("--answers-dir requires a value")?;
+                answers_dir = Some(PathBuf::from(dir));
+            }
+            "--record" => record = true,
+            "--time" | "-t" => time = true,
+            "--bench" => {
+                let iterations = args.next().ok_or("--bench requires a value")?;
+                match iterations.parse::<usize>() {
+                    Ok(iterations) if iterations > 0 => bench = Some(iterations),
+                    _ => return Err(format!("Invalid iteration count '{iterations}'")),
+                }
+            }
+            "--jobs" | "-j" => {
+                let count = args.next().ok_or("--jobs requires a value")?;
+                match count.parse::<usize>() {
+                    Ok(count) if count > 0 => jobs = count,
+                    _ => return Err(format!("Invalid job count '{count}'")),
+                }
+            }
+            "--parallel" => {
+                jobs = thread::available_parallelism().map_or(1, |count| count.get());
+                time = true;
+            }
+            "--json" => {
+                let path = args.next().ok_or("--json requires a value")?;
+                json = Some(PathBuf::from(path));
+            }
+            flag if flag.starts_with('-') => return Err(format!("Unknown option '{flag}'")),
+            _ => positional.push(arg),
+        }
+    }
+
+    if json.is_some() && bench.is_none() {
+        return Err("--json can only be used with --bench".to_string());
+    }
+    // Benchmarks don't check answers and solve one day at a time so that the days
+    // don't slow each other down.
+    if bench.is_some() {
+        let ignored = [
+            ("--record", record),
+            ("--answers-dir", answers_dir.is_some()),
+            ("--jobs", jobs > 1),
+        ];
+        if let Some((flag, _)) = ignored.iter().find(|(_, given)| *given) {
+            return Err(format!("{flag} can't be used with --bench"));
+        }
+    }
+
+    let (year, days) = match positional.as_slice() {
+        [] => return Ok(Command::Help),
+        [all] if all == "all" => (None, DaySelection::All),
+        [year] => (Some(parse_year(year)?), DaySelection::All),
+        [year, days] => (Some(parse_year(year)?), parse_days(days)?),
+        _ => return Err("Too many arguments".to_string()),
+    };
+    let single_day = year.is_some() && (1..=25).filter(|&day| days.contains(day)).count() == 1;
+    if input.is_some() && !single_day {
+        return Err("--input can only be used when a single day is selected".to_string());
+    }
+
+    return Ok(Command::Run(Args {
+        year,
+        days,
+        part,
+        input,
+        input_dir,
+        offline,
+        answers_dir,
+        record,
+        time,
+        bench,
+        json,
+        jobs,
+    }));
+}
+
+// Parse the arguments of the `scaffold` subcommand. The title may be split over
+// several arguments when it isn't quoted.
+fn parse_scaffold(args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let args: Vec<String> = args.collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        return Ok(Command::Help);
+    }
+
+    return match args.as_slice() {
+        [year, day, title @ ..] if !title.is_empty() => Ok(Command::Scaffold {
+            year: parse_year(year)?,
+            day: parse_day(day)?,
+            title: title.join(" "),
+        }),
+        _ => Err("scaffold requires a year, a day and the puzzle title".to_string()),
+    };
+}
+
+// Parse the arguments of the `generate` subcommand.
+fn parse_generate(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut positional = vec![];
+    let mut seed = 0;
+    let mut size = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--seed" => {
+                let value = args.next().ok_or("--seed requires a value")?;
+                seed = value
+                    .parse::<u64>()
+                    .map_err(|_| format!("Invalid seed '{value}'"))?;
+            }
+            "--size" => {
+                let value = args.next().ok_or("--size requires a value")?;
+                match value.parse::<usize>() {
+                    Ok(value) if value > 0 => size = Some(value),
+                    _ => return Err(format!("Invalid size '{value}'")),
+                }
+            }
+            flag if flag.starts_with('-') => return Err(format!("Unknown option '{flag}'")),
+            _ => positional.push(arg),
+        }
+    }
+
+    return match positional.as_slice() {
+        [year, day] => Ok(Command::Generate {
+            year: parse_year(year)?,
+            day: parse_day(day)?,
+            seed,
+            size,
+        }),
+        _ => Err("generate requires a year and a day".to_string()),
+    };
+}
+
+fn parse_year(input: &str) -> Result<u32, String> {
+    return input
+        .parse::<u32>()
+        .map_err(|_| format!("Invalid year '{input}'"));
+}
+
+fn run(puzzles: &[Puzzle], args: &Args) -> ExitCode {
+    let selected: Vec<&Puzzle> = puzzles
+        .iter()
+        .filter(|puzzle| args.selects(puzzle))
+        .collect();
+
+    if selected.is_empty() {
+        eprintln!("No registered puzzles match the selection");
+        return ExitCode::FAILURE;
+    }
+
+    let mut provider = match &args.input_dir {
+        Some(dir) => InputProvider::new(dir),
+        // Only inputs from the default directory are built into the binary.
+        None => InputProvider::default().with_embedded(),
+    };
+    if let Some(fetcher) = HttpFetcher::from_env().filter(|_| !args.offline) {
+        provider = provider.with_fetcher(Arc::new(fetcher));
+    }
+    // Parsing makes sure --input comes with a single day.
+    if let Some(source) = &args.input {
+        provider = provider.with_override(selected[0].year, selected[0].day, source.clone());
+    }
+
+    if let Some(iterations) = args.bench {
+        return run_benchmarks(&selected, &provider, args, iterations);
+    }
+
+    let answers_dir = args.answers_dir.clone().unwrap_or(PathBuf::from("answers"));
+    let mut store = match AnswerStore::load(answers_dir) {
+        Ok(store) => store,
+        Err(error) => {
+            eprintln!("Failed to load answers: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // Answers for an arbitrary input file can't be compared with the stored answers.
+    let verify = args.input.is_none();
+    let mut summary = Summary::default();
+
+    let mut exit_code = ExitCode::SUCCESS;
+    let start = Instant::now();
+    let _panic_hook = puzzle::capture_panics();
+
+    pool::for_each_ordered(
+        &selected,
+        args.jobs,
+        |puzzle| puzzle.run(&provider, args.part),
+        |puzzle, outcome| {
+            println!("{} day {:02}", puzzle.year, puzzle.day);
+
+            // Puzzle::run already catches panics in the solution, so this only happens
+            // if reading the input panics.
+            let outcome = outcome
+                .unwrap_or_else(|payload| Err(Failure::Panic(puzzle::panic_message(payload))));
+            let (answers, timings) = match outcome {
+                Ok(outcome) => outcome,
+                Err(failure) => {
+                    println!("  Error: {failure}");
+                    if let Failure::Parse { error, .. } = &failure {
+                        print_snippet(error);
+                    }
+                    summary.errors += 1;
+                    return;
+                }
+            };
+
+            if args.time {
+                println!("  Parse: {}", format_duration(timings.parse));
+            }
+            let parts = [
+                (Part::One, answers.part1, timings.part1),
+                (Part::Two, answers.part2, timings.part2),
+            ];
+            for (part, answer, duration) in parts {
+                let Some(answer) = answer else {
+                    continue;
+                };
+                let label = match (part, duration) {
+                    (Part::One, Some(duration)) if args.time => {
+                        format!("Part 1 [{}]", format_duration(duration))
+                    }
+                    (Part::Two, Some(duration)) if args.time => {
+                        format!("Part 2 [{}]", format_duration(duration))
+                    }
+                    (Part::One, _) => "Part 1".to_string(),
+                    (Part::Two, _) => "Part 2".to_string(),
+                };
+
+                if !verify {
+                    println!("  {label}: {answer}");
+                    continue;
+                }
+
+                let status = match store.check(puzzle.year, puzzle.day, part, &answer) {
+                    Verdict::Pass => {
+                        summary.passed += 1;
+                        "pass".to_string()
+                    }
+                    Verdict::Fail { expected } => {
+                        summary.failed += 1;
+                        format!("FAIL, expected {expected}")
+                    }
+                    Verdict::Unknown if args.record => {
+                        store.record(puzzle.year, puzzle.day, part, &answer);
+                        summary.recorded += 1;
+                        "recorded".to_string()
+                    }
+                    Verdict::Unknown => {
+                        summary.unknown += 1;
+                        "unknown".to_string()
+                    }
+                };
+                println!("  {label}: {answer} ({status})");
+            }
+        },
+    );
+
+    if args.time {
+        println!(
+            "\nSolved {} day(s) in {} using {} job(s)",
+            selected.len(),
+            format_duration(start.elapsed()),
+            args.jobs.min(selected.len())
+        );
+    }
+    if summary.errors > 0 {
+        println!("{} day(s) failed to run", summary.errors);
+        exit_code = ExitCode::FAILURE;
+    }
+    if verify {
+        println!(
+            "\nAnswers: {} passed, {} failed, {} unknown, {} recorded",
+            summary.passed, summary.failed, summary.unknown, summary.recorded
+        );
+    }
+
+    if summary.recorded > 0 {
+        if let Err(error) = store.save() {
+            eprintln!("Failed to save answers: {error}");
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+
+    if summary.failed > 0 {
+        exit_code = ExitCode::FAILURE;
+    }
+
+    return exit_code;
+}
+
+// Show the offending input line under a parse error, indented to match the report.
+fn print_snippet(error: &Error) {
+    if let Some(snippet) = error.snippet() {
+        for line in snippet.lines() {
+            println!("    {line}");
+        }
+    }
+}
+
+fn run_benchmarks(
+    selected: &[&Puzzle],
+    provider: &InputProvider,
+    args: &Args,
+    iterations: usize,
+) -> ExitCode {
+    let mut exit_code = ExitCode::SUCCESS;
+    let mut benchmarks = vec![];
+    let _panic_hook = puzzle::capture_panics();
+
+    for puzzle in selected {
+        let source = match provider.resolve(puzzle.year, puzzle.day) {
+            Ok(source) => source,
+            Err(error) => {
+                eprintln!("{} day {:02}: {error}", puzzle.year, puzzle.day);
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!(
+                    "{} day {:02}: failed to read {source}: {error}",
+                    puzzle.year, puzzle.day
+                );
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        match bench::run(puzzle, &input, &source.to_string(), args.part, iterations) {
+            Ok(benchmark) => benchmarks.push(benchmark),
+            Err(failure) => {
+                eprintln!("{} day {:02}: {failure}", puzzle.year, puzzle.day);
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    bench::sort(&mut benchmarks);
+    println!("{iterations} iteration(s) per day\n");
+    print!("{}", bench::table(&benchmarks));
+
+    if let Some(path) = &args.json {
+        if let Err(error) = fs::write(path, bench::to_json(&benchmarks, iterations)) {
+            eprintln!("Failed to write {}: {error}", path.display());
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+
+    return exit_code;
+}
+
+#[derive(Default)]
+struct Summary {
+    errors: u32,
+    passed: u32,
+    failed: u32,
+    unknown: u32,
+    recorded: u32,
+}
+
+// Entry point for the binary. `puzzles` is the registry of every available day.
+pub fn main(puzzles: &[Puzzle]) -> ExitCode {
+    return match parse(std::env::args().skip(1)) {
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Run(args)) => run(puzzles, &args),
+        Ok(Command::Scaffold { year, day, title }) => {
+            match scaffold::scaffold(Path::new("."), year, day, &title) {
+                Ok(written) => {
+                    for path in written {
+                        println!("Wrote {}", path.display());
+                    }
+                    ExitCode::SUCCESS
+                }
+                Err(error) => {
+                    eprintln!("Failed to scaffold {year} day {day:02}: {error}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Ok(Command::Generate {
+            year,
+            day,
+            seed,
+            size,
+        }) => {
+            let input = match year {
+                2024 => year2024::generate(day, seed, size),
+                _ => None,
+            };
+            match input {
+                Some(input) => {
+                    print!("{input}");
+                    ExitCode::SUCCESS
+                }
+                None => {
+                    eprintln!("There's no input generator for {year} day {day:02}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_line(line: &str) -> Result<Command, String> {
+        return parse(line.split_whitespace().map(String::from));
+    }
+
+    fn parse_run(line: &str) -> Args {
+        return match parse_line(line) {
+            Ok(Command::Run(args)) => args,
+            other => panic!("expected '{line}' to run puzzles, got {other:?}"),
+        };
+    }
+
+    #[test]
+    fn parses_day_selections() {
+        let args = parse_run("2024 1-5");
+        assert_eq!(args.year, Some(2024));
+        assert_eq!(args.days, DaySelection::Days(vec![1..=5]));
+        assert_eq!((args.part, args.bench, args.jobs), (None, None, 1));
+
+        let args = parse_run("2024 1,3,10-12");
+        assert_eq!(args.days, DaySelection::Days(vec![1..=1, 3..=3, 10..=12]));
+        assert!(args.days.contains(11) && !args.days.contains(2));
+
+        assert_eq!(parse_run("2024 all").days, DaySelection::All);
+        let args = parse_run("all");
+        assert_eq!((args.year, args.days), (None, DaySelection::All));
+    }
+
+    #[test]
+    fn missing_day_selection_selects_the_whole_year() {
+        let args = parse_run("2024 --time");
+        assert_eq!(
+            (args.year, args.days, args.time),
+            (Some(2024), DaySelection::All, true)
+        );
+
+        // Without a year there is nothing to run.
+        assert_eq!(parse_line(""), Ok(Command::Help));
+        assert_eq!(parse_line("--offline"), Ok(Command::Help));
+    }
+
+    #[test]
+    fn rejects_days_out_of_range() {
+        for days in ["0", "26", "0-3", "20-26", "1,26"] {
+            let error = parse_line(&format!("2024 {days}")).unwrap_err();
+            assert!(
+                error.contains("expected a number from 1 to 25"),
+                "{days}: {error}"
+            );
+        }
+        assert_eq!(
+            parse_line("2024 1,,3"),
+            Err("Invalid day '', expected a number from 1 to 25".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_reversed_day_ranges() {
+        assert_eq!(
+            parse_line("2024 12-10"),
+            Err("Invalid day range '12-10'".to_string())
+        );
+        assert_eq!(parse_run("2024 7-7").days, DaySelection::Days(vec![7..=7]));
+    }
+
+    #[test]
+    fn input_requires_a_single_day() {
+        let args = parse_run("2024 16 --input other.txt");
+        assert_eq!(args.input, Some(Source::File(PathBuf::from("other.txt"))));
+        assert_eq!(parse_run("2024 3-3 -i -").input, Some(Source::Stdin));
+
+        for line in ["2024 1-2", "2024 1,3", "2024", "all"] {
+            assert_eq!(
+                parse_line(&format!("{line} --input other.txt")),
+                Err("--input can only be used when a single day is selected".to_string()),
+                "{line}"
+            );
+        }
+        assert_eq!(
+            parse_line("2024 1 --input"),
+            Err("--input requires a value".to_string())
+        );
+    }
+
+    #[test]
+    fn json_requires_bench() {
+        assert_eq!(
+            parse_line("2024 --json timings.json"),
+            Err("--json can only be used with --bench".to_string())
+        );
+        let args = parse_run("2024 --bench 3 --json timings.json");
+        assert_eq!(args.bench, Some(3));
+        assert_eq!(args.json, Some(PathBuf::from("timings.json")));
+    }
+
+    #[test]
+    fn bench_rejects_options_it_would_ignore() {
+        for option in ["--record", "--answers-dir answers", "--jobs 4"] {
+            let error = parse_line(&format!("2024 --bench 3 {option}")).unwrap_err();
+            assert!(
+                error.ends_with("can't be used with --bench"),
+                "{option}: {error}"
+            );
+        }
+        assert_eq!(parse_run("2024 --bench 3 --jobs 1").bench, Some(3));
+    }
+
+    #[test]
+    fn rejects_invalid_parts() {
+        assert_eq!(parse_run("2024 1 --part 2").part, Some(Part::Two));
+        assert_eq!(parse_run("2024 1 -p 1").part, Some(Part::One));
+        assert_eq!(
+            parse_line("2024 1 --part 3"),
+            Err("Invalid part '3', expected 1 or 2".to_string())
+        );
+        assert_eq!(
+            parse_line("2024 1 --part"),
+            Err("--part requires a value".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_options() {
+        assert_eq!(
+            parse_line("2024 1 --fast"),
+            Err("Unknown option '--fast'".to_string())
+        );
+        assert_eq!(
+            parse_line("generate 2024 1 -x"),
+            Err("Unknown option '-x'".to_string())
+        );
+        assert_eq!(
+            parse_line("2024 1 2"),
+            Err("Too many arguments".to_string())
+        );
+    }
+}
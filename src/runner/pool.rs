@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+// Run `work` on every item using up to `workers` threads.
+// Results are handed to `report` in the original order of `items`, each one as soon as
+// every earlier item has finished, so output stays stable no matter which thread wins.
+// A panic in `work` is reported as the `Err` result of its item and the other items
+// still run.
+pub fn for_each_ordered<T, R, W, F>(items: &[T], workers: usize, work: W, mut report: F)
+where
+    T: Sync,
+    R: Send,
+    W: Fn(&T) -> R + Sync,
+    F: FnMut(&T, thread::Result<R>),
+{
+    let workers = workers.clamp(1, items.len().max(1));
+    let next_index = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (next_index, work) = (&next_index, &work);
+
+            scope.spawn(move || loop {
+                let index = next_index.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| work(item)));
+                if sender.send((index, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        // Drop the original sender so the receiver ends once every worker is done.
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_report = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_report) {
+                report(&items[next_report], result);
+                next_report += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    // Run `work` on `items` and collect what's reported, with the panic message of
+    // the items that panicked.
+    fn run_all(
+        items: &[u32],
+        workers: usize,
+        work: impl Fn(&u32) -> u32 + Sync,
+    ) -> Vec<(u32, Result<u32, String>)> {
+        let mut reported = vec![];
+        for_each_ordered(items, workers, work, |&item, result| {
+            let result = result.map_err(|payload| *payload.downcast::<String>().unwrap());
+            reported.push((item, result));
+        });
+        return reported;
+    }
+
+    #[test]
+    fn reports_in_order_when_items_finish_out_of_order() {
+        // The first item waits until the last one is done, so it finishes last.
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Mutex::new(receiver);
+        let finished = Mutex::new(vec![]);
+
+        let reported = run_all(&[0, 1, 2, 3], 2, |&item| {
+            if item == 0 {
+                receiver.lock().unwrap().recv().unwrap();
+            }
+            finished.lock().unwrap().push(item);
+            if item == 3 {
+                sender.send(()).unwrap();
+            }
+            return item * 10;
+        });
+
+        assert_eq!(finished.into_inner().unwrap(), [1, 2, 3, 0]);
+        assert_eq!(
+            reported,
+            [(0, Ok(0)), (1, Ok(10)), (2, Ok(20)), (3, Ok(30))]
+        );
+    }
+
+    #[test]
+    fn uses_at_most_one_worker_per_item() {
+        for (workers, expected_threads) in [(1, 1), (16, 3)] {
+            let threads = Mutex::new(HashSet::new());
+            let reported = run_all(&[1, 2, 3], workers, |&item| {
+                threads.lock().unwrap().insert(thread::current().id());
+                // Keep each worker busy so every one of them gets an item.
+                thread::sleep(std::time::Duration::from_millis(20));
+                return item + 1;
+            });
+
+            assert_eq!(reported, [(1, Ok(2)), (2, Ok(3)), (3, Ok(4))]);
+            assert!(threads.into_inner().unwrap().len() <= expected_threads);
+        }
+
+        assert_eq!(run_all(&[], 4, |&item| item), []);
+    }
+
+    #[test]
+    fn a_panicking_item_is_reported_and_the_rest_still_run() {
+        for workers in [1, 3] {
+            let reported = run_all(&[1, 2, 3, 4, 5], workers, |&item| {
+                if item == 2 {
+                    panic!("item {item} failed");
+                }
+                return item * 2;
+            });
+
+            assert_eq!(
+                reported,
+                [
+                    (1, Ok(2)),
+                    (2, Err("item 2 failed".to_string())),
+                    (3, Ok(6)),
+                    (4, Ok(8)),
+                    (5, Ok(10)),
+                ],
+                "{workers} worker(s)"
+            );
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::error::Error;
+use crate::input::provider::InputProvider;
+use crate::solution::{self, Answers, Outcome, Part, Solution, Timings};
+use std::cell::RefCell;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+// A single entry in the puzzle registry. Entries are built in `main.rs` from the
+// `yearYYYY::dayNN` module names so adding a day only requires one new line there.
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u8,
+    pub solve: fn(&str, Option<Part>) -> Outcome,
+}
+
+// Why a puzzle couldn't produce answers.
+#[derive(Debug)]
+pub enum Failure {
+    Input(String),
+    Parse { source: String, error: Error },
+    Panic(String),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Failure::Input(message) => write!(f, "failed to read input: {message}"),
+            Failure::Parse { source, error } => write!(f, "invalid input in {source}: {error}"),
+            Failure::Panic(message) => write!(f, "panicked: {message}"),
+        };
+    }
+}
+
+type PanicHook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send>;
+
+thread_local! {
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Replace the default panic hook, which prints a backtrace for every panic, with one
+// that only records where the panic happened so it can be reported with the failure.
+// Returns a guard that restores the previous hook when dropped.
+pub fn capture_panics() -> impl Drop {
+    struct RestoreHook(Option<PanicHook>);
+
+    impl Drop for RestoreHook {
+        fn drop(&mut self) {
+            if let Some(hook) = self.0.take() {
+                panic::set_hook(hook);
+            }
+        }
+    }
+
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        let location = info
+            .location()
+            .map(|location| format!("{}:{}", location.file(), location.line()));
+        PANIC_LOCATION.with(|cell| *cell.borrow_mut() = location);
+    }));
+
+    return RestoreHook(Some(previous));
+}
+
+pub fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    };
+
+    return match PANIC_LOCATION.with(|cell| cell.borrow_mut().take()) {
+        Some(location) => format!("{message} (at {location})"),
+        None => message,
+    };
+}
+
+impl Puzzle {
+    // Build a registry entry from module names such as ("year2024", "day01").
+    pub fn new<S: Solution>(year_module: &str, day_module: &str) -> Self {
+        let year = year_module
+            .strip_prefix("year")
+            .and_then(|year| year.parse::<u32>().ok())
+            .expect("Year module must be named like \"year2024\"");
+        let day = day_module
+            .strip_prefix("day")
+            .and_then(|day| day.parse::<u8>().ok())
+            .expect("Day module must be named like \"day01\"");
+
+        return Puzzle {
+            year,
+            day,
+            solve: solution::solve::<S>,
+        };
+    }
+
+    // Read the input and solve the puzzle.
+    pub fn run(
+        &self,
+        provider: &InputProvider,
+        part: Option<Part>,
+    ) -> Result<(Answers, Timings), Failure> {
+        let source = provider
+            .resolve(self.year, self.day)
+            .map_err(|error| Failure::Input(error.to_string()))?;
+        let input = source
+            .read()
+            .map_err(|error| Failure::Input(format!("{source}: {error}")))?;
+
+        return self.solve_input(&input, &source.to_string(), part);
+    }
+
+    // Solve the puzzle for an input that has already been read. `source` says where the
+    // input came from in parse errors. A panic inside the solution is caught and
+    // returned as a failure so that it doesn't abort any other puzzle.
+    pub fn solve_input(
+        &self,
+        input: &str,
+        source: &str,
+        part: Option<Part>,
+    ) -> Result<(Answers, Timings), Failure> {
+        return panic::catch_unwind(AssertUnwindSafe(|| (self.solve)(input, part)))
+            .map_err(|payload| Failure::Panic(panic_message(payload)))?
+            .map_err(|error| Failure::Parse {
+                source: source.to_string(),
+                error,
+            });
+    }
+}
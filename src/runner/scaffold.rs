@@ -0,0 +1,386 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// A new day with `process_file`, `part1` and `part2` stubs. The `Solution` impl is what
+// runs the day, so it takes the place of the `run` function days used to have.
+const TEMPLATE: &str = r#"use crate::error::{self, Error};
+use crate::solution::Solution;
+
+type Input = Vec<String>;
+
+fn process_file(input: &str) -> Result<Input, Error> {
+    return Ok(error::numbered(input)
+        .map(|line| line.text.to_string())
+        .collect());
+}
+
+fn part1(_input: &Input) -> usize {
+    todo!()
+}
+
+fn part2(_input: &Input) -> usize {
+    todo!()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Input;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    fn part1(input: &Self::Input) -> usize {
+        return part1(input);
+    }
+
+    fn part2(input: &Self::Input) -> usize {
+        return part2(input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+";
+
+    #[test]
+    #[ignore = "add the puzzle example"]
+    fn part1_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&input), 0);
+    }
+
+    #[test]
+    #[ignore = "add the puzzle example"]
+    fn part2_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&input), 0);
+    }
+}
+"#;
+
+// Create the source file of a new day and register it everywhere a day has to be listed:
+// the module tree in `lib.rs`, the puzzle registry in `main.rs` and the table in the README.
+// Returns the files that were written.
+pub fn scaffold(root: &Path, year: u32, day: u8, title: &str) -> io::Result<Vec<PathBuf>> {
+    let source = root.join(format!("src/year{year}/day{day:02}.rs"));
+    if source.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", source.display()),
+        ));
+    }
+
+    // Prepare every edit before writing anything so a failure leaves the tree untouched.
+    let writes = [
+        updated(root, "src/lib.rs", |lib| register_module(lib, year, day))?,
+        updated(root, "src/main.rs", |main| register_puzzle(main, year, day))?,
+        updated(root, "README.md", |readme| {
+            add_readme_row(readme, year, day, title)
+        })?,
+    ];
+
+    fs::create_dir_all(source.parent().expect("The source file is inside src/"))?;
+    fs::write(&source, TEMPLATE)?;
+
+    let mut written = vec![source];
+    for (path, contents) in writes {
+        fs::write(&path, contents)?;
+        written.push(path);
+    }
+
+    return Ok(written);
+}
+
+// Read `file` and apply `edit` to its contents.
+fn updated(
+    root: &Path,
+    file: &str,
+    edit: impl FnOnce(&str) -> Result<String, String>,
+) -> io::Result<(PathBuf, String)> {
+    let path = root.join(file);
+    let contents = edit(&fs::read_to_string(&path)?).map_err(|message| {
+        return io::Error::new(io::ErrorKind::InvalidData, format!("{file}: {message}"));
+    })?;
+    return Ok((path, contents));
+}
+
+// Split text into lines, remembering whether it ended with a newline.
+fn split_lines(text: &str) -> (Vec<String>, bool) {
+    return (
+        text.lines().map(|line| line.to_string()).collect(),
+        text.ends_with('\n'),
+    );
+}
+
+fn join_lines(lines: Vec<String>, trailing_newline: bool) -> String {
+    let mut text = lines.join("\n");
+    if trailing_newline {
+        text.push('\n');
+    }
+    return text;
+}
+
+// Add `pub mod dayNN;` to the year's module block in `lib.rs`, creating the block if
+// this is the first day of the year. Days and years are kept in order.
+pub fn register_module(lib: &str, year: u32, day: u8) -> Result<String, String> {
+    let (mut lines, trailing_newline) = split_lines(lib);
+    let header = format!("pub mod year{year} {{");
+    let entry = format!("    pub mod day{day:02};");
+
+    let Some(start) = lines.iter().position(|line| *line == header) else {
+        // Years are listed in order, so the new block goes before the first later year.
+        let position = lines
+            .iter()
+            .position(|line| {
+                return line
+                    .strip_prefix("pub mod year")
+                    .and_then(|rest| rest.strip_suffix(" {"))
+                    .and_then(|other| other.parse::<u32>().ok())
+                    .is_some_and(|other| other > year);
+            })
+            .unwrap_or(lines.len());
+
+        let mut block = vec![header, entry, "}".to_string()];
+        if position < lines.len() {
+            block.push(String::new());
+        } else if lines.last().is_some_and(|line| !line.is_empty()) {
+            block.insert(0, String::new());
+        }
+        lines.splice(position..position, block);
+        return Ok(join_lines(lines, trailing_newline));
+    };
+
+    let end = lines[start..]
+        .iter()
+        .position(|line| line == "}")
+        .map(|offset| start + offset)
+        .ok_or(format!("the `year{year}` module block isn't closed"))?;
+
+    if lines[start..end].contains(&entry) {
+        return Err(format!("day{day:02} is already registered"));
+    }
+
+    let position = (start + 1..end)
+        .find(|&index| lines[index] > entry)
+        .unwrap_or(end);
+    lines.insert(position, entry);
+
+    return Ok(join_lines(lines, trailing_newline));
+}
+
+// Add `puzzle!(yearYYYY, dayNN),` to the registry in `main.rs`, in order.
+pub fn register_puzzle(main: &str, year: u32, day: u8) -> Result<String, String> {
+    let (mut lines, trailing_newline) = split_lines(main);
+    let entry = format!("puzzle!(year{year}, day{day:02}),");
+
+    let registered: Vec<usize> = (0..lines.len())
+        .filter(|&index| lines[index].trim_start().starts_with("puzzle!("))
+        .collect();
+    let (Some(&first), Some(&last)) = (registered.first(), registered.last()) else {
+        return Err("no `puzzle!` entries to add the day to".to_string());
+    };
+
+    if registered.iter().any(|&index| lines[index].trim() == entry) {
+        return Err(format!("year{year}::day{day:02} is already registered"));
+    }
+
+    let indent: String = lines[first]
+        .chars()
+        .take_while(|char| char.is_whitespace())
+        .collect();
+    let position = registered
+        .iter()
+        .copied()
+        .find(|&index| lines[index].trim() > entry.as_str())
+        .unwrap_or(last + 1);
+    lines.insert(position, format!("{indent}{entry}"));
+
+    return Ok(join_lines(lines, trailing_newline));
+}
+
+// Escape the characters in a puzzle title that Markdown would otherwise read as part of
+// the table, the link or inline formatting.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::new();
+    for char in text.chars() {
+        if "\\|[]<>&*_`~".contains(char) {
+            escaped.push('\\');
+        }
+        escaped.push(char);
+    }
+    return escaped;
+}
+
+// Add a row for the day to the year's table in the README, creating the table if this
+// is the first day of the year. The newest year is listed first.
+pub fn add_readme_row(readme: &str, year: u32, day: u8, title: &str) -> Result<String, String> {
+    let (mut lines, trailing_newline) = split_lines(readme);
+    let heading = format!("### {year}");
+    let title = escape_markdown(title);
+    let row = format!(
+        "| {day:<3} | [{title}](https://adventofcode.com/{year}/day/{day}) | [Source](src/year{year}/day{day:02}.rs) |"
+    );
+
+    let row_day = |line: &str| -> Option<u8> {
+        return line
+            .strip_prefix('|')?
+            .split('|')
+            .next()?
+            .trim()
+            .parse::<u8>()
+            .ok();
+    };
+
+    let Some(start) = lines.iter().position(|line| *line == heading) else {
+        let code = lines
+            .iter()
+            .position(|line| line == "## Code")
+            .ok_or("there is no `## Code` section")?;
+        let position = (code + 1..lines.len())
+            .find(|&index| {
+                return lines[index]
+                    .strip_prefix("### ")
+                    .and_then(|other| other.parse::<u32>().ok())
+                    .is_some_and(|other| other < year);
+            })
+            .unwrap_or(lines.len());
+
+        let mut table = vec![
+            heading,
+            "| Day | Problem | Solution |".to_string(),
+            "| --- | ------- | -------- |".to_string(),
+            row,
+        ];
+        if position < lines.len() {
+            table.push(String::new());
+        } else if lines.last().is_some_and(|line| !line.is_empty()) {
+            table.insert(0, String::new());
+        }
+        lines.splice(position..position, table);
+        return Ok(join_lines(lines, trailing_newline));
+    };
+
+    // The table starts after the heading, column names and separator.
+    let first_row = start + 3;
+    let end = (first_row..lines.len())
+        .find(|&index| !lines[index].starts_with('|'))
+        .unwrap_or(lines.len());
+
+    if (first_row..end).any(|index| row_day(&lines[index]) == Some(day)) {
+        return Err(format!("day {day} of {year} is already listed"));
+    }
+
+    let position = (first_row..end)
+        .find(|&index| row_day(&lines[index]).is_some_and(|other| other > day))
+        .unwrap_or(end);
+    lines.insert(position, row);
+
+    return Ok(join_lines(lines, trailing_newline));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "\
+pub mod util {
+    pub mod grid;
+}
+
+pub mod year2024 {
+    pub mod day01;
+    pub mod day03;
+}
+";
+
+    const MAIN: &str = "\
+fn main() -> ExitCode {
+    let puzzles = [
+        puzzle!(year2024, day01),
+        puzzle!(year2024, day03),
+    ];
+
+    return cli::main(&puzzles);
+}
+";
+
+    const README: &str = "\
+## Code
+### 2024
+| Day | Problem | Solution |
+| --- | ------- | -------- |
+| 1   | [Historian Hysteria](https://adventofcode.com/2024/day/1) | [Source](src/year2024/day01.rs) |
+| 3   | [Mull It Over](https://adventofcode.com/2024/day/3) | [Source](src/year2024/day03.rs) |";
+
+    #[test]
+    fn registers_a_module_in_order() {
+        let lib = register_module(LIB, 2024, 2).unwrap();
+        assert!(lib.contains("    pub mod day01;\n    pub mod day02;\n    pub mod day03;\n}\n"));
+        assert!(register_module(&lib, 2024, 2).is_err());
+    }
+
+    #[test]
+    fn registers_a_module_for_a_new_year() {
+        let lib = register_module(LIB, 2025, 1).unwrap();
+        assert!(
+            lib.ends_with("    pub mod day03;\n}\n\npub mod year2025 {\n    pub mod day01;\n}\n")
+        );
+    }
+
+    #[test]
+    fn registers_a_puzzle_in_order() {
+        let main = register_puzzle(MAIN, 2024, 2).unwrap();
+        assert!(main.contains(
+            "        puzzle!(year2024, day01),\n        puzzle!(year2024, day02),\n        puzzle!(year2024, day03),\n    ];"
+        ));
+
+        let main = register_puzzle(MAIN, 2025, 1).unwrap();
+        assert!(main.contains(
+            "        puzzle!(year2024, day03),\n        puzzle!(year2025, day01),\n    ];"
+        ));
+        assert!(register_puzzle(MAIN, 2024, 3).is_err());
+    }
+
+    #[test]
+    fn adds_a_readme_row_in_order() {
+        let readme = add_readme_row(README, 2024, 2, "Red-Nosed Reports").unwrap();
+        let lines: Vec<&str> = readme.lines().collect();
+        assert_eq!(
+            lines[5],
+            "| 2   | [Red-Nosed Reports](https://adventofcode.com/2024/day/2) | [Source](src/year2024/day02.rs) |"
+        );
+        assert!(lines[6].starts_with("| 3   |"));
+        assert!(add_readme_row(&readme, 2024, 2, "Red-Nosed Reports").is_err());
+    }
+
+    #[test]
+    fn escapes_the_title_in_a_readme_row() {
+        let readme = add_readme_row(README, 2024, 2, "Test & <Day> | pipe").unwrap();
+        assert_eq!(
+            readme.lines().nth(5).unwrap(),
+            r"| 2   | [Test \& \<Day\> \| pipe](https://adventofcode.com/2024/day/2) | [Source](src/year2024/day02.rs) |"
+        );
+        assert_eq!(escape_markdown(r"[a_b]\*"), r"\[a\_b\]\\\*");
+    }
+
+    #[test]
+    fn adds_a_readme_table_for_a_new_year() {
+        let readme = add_readme_row(README, 2025, 12, "Example").unwrap();
+        let lines: Vec<&str> = readme.lines().collect();
+        assert_eq!(lines[1], "### 2025");
+        assert_eq!(
+            lines[4],
+            "| 12  | [Example](https://adventofcode.com/2025/day/12) | [Source](src/year2025/day12.rs) |"
+        );
+        assert_eq!(lines[5], "");
+        assert_eq!(lines[6], "### 2024");
+    }
+}
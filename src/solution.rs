@@ -0,0 +1,73 @@
+use crate::error::Error;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+// Every day implements `Solution` so it can be parsed and solved generically.
+// The parsed input is shared by both parts, so parsing only happens once.
+pub trait Solution {
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+}
+
+// The displayed answers of a solution. A part is `None` when it wasn't run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+// How long each step of a solution took. A part is `None` when it wasn't run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        return self.parse + self.part1.unwrap_or_default() + self.part2.unwrap_or_default();
+    }
+}
+
+// The answers and timings of a solved puzzle, or why its input couldn't be parsed.
+pub type Outcome = Result<(Answers, Timings), Error>;
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    return (result, start.elapsed());
+}
+
+// Parse the input and solve the requested part, or both parts when `part` is `None`.
+// Answers are formatted inside the timed section so lazy `Display` work is included.
+pub fn solve<S: Solution>(input: &str, part: Option<Part>) -> Outcome {
+    let (input, parse) = timed(|| S::parse(input));
+    let input = input?;
+
+    let part1 = (part != Some(Part::Two)).then(|| timed(|| S::part1(&input).to_string()));
+    let part2 = (part != Some(Part::One)).then(|| timed(|| S::part2(&input).to_string()));
+
+    let answers = Answers {
+        part1: part1.as_ref().map(|(answer, _)| answer.clone()),
+        part2: part2.as_ref().map(|(answer, _)| answer.clone()),
+    };
+    let timings = Timings {
+        parse,
+        part1: part1.map(|(_, duration)| duration),
+        part2: part2.map(|(_, duration)| duration),
+    };
+
+    return Ok((answers, timings));
+}
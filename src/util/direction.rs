@@ -0,0 +1,155 @@
+use crate::util::point::Vector;
+
+// A heading on a grid where up is towards the first row.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    // The four headings along the rows and columns, clockwise from up.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    // The four headings between the cardinal ones, clockwise from up right.
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::UpRight,
+        Direction::DownRight,
+        Direction::DownLeft,
+        Direction::UpLeft,
+    ];
+
+    // Every heading, clockwise from up.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    // Parse a move drawn as an arrow: '^', 'v', '<' or '>'.
+    pub fn from_arrow(arrow: char) -> Option<Self> {
+        return match arrow {
+            '^' => Some(Direction::Up),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            '>' => Some(Direction::Right),
+            _ => None,
+        };
+    }
+
+    // Parse a compass point: 'N', 'E', 'S' or 'W'.
+    pub fn from_compass(point: char) -> Option<Self> {
+        return match point {
+            'N' => Some(Direction::Up),
+            'E' => Some(Direction::Right),
+            'S' => Some(Direction::Down),
+            'W' => Some(Direction::Left),
+            _ => None,
+        };
+    }
+
+    // The arrow the heading is drawn with. Diagonals don't have an ASCII arrow.
+    pub fn arrow(self) -> char {
+        return match self {
+            Direction::Up => '^',
+            Direction::UpRight => '↗',
+            Direction::Right => '>',
+            Direction::DownRight => '↘',
+            Direction::Down => 'v',
+            Direction::DownLeft => '↙',
+            Direction::Left => '<',
+            Direction::UpLeft => '↖',
+        };
+    }
+
+    // The step that moves one cell in this direction.
+    pub fn offset(self) -> Vector {
+        return match self {
+            Direction::Up => Vector::new(0, -1),
+            Direction::UpRight => Vector::new(1, -1),
+            Direction::Right => Vector::new(1, 0),
+            Direction::DownRight => Vector::new(1, 1),
+            Direction::Down => Vector::new(0, 1),
+            Direction::DownLeft => Vector::new(-1, 1),
+            Direction::Left => Vector::new(-1, 0),
+            Direction::UpLeft => Vector::new(-1, -1),
+        };
+    }
+
+    pub fn is_vertical(self) -> bool {
+        return matches!(self, Direction::Up | Direction::Down);
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        return matches!(self, Direction::Left | Direction::Right);
+    }
+
+    // Turn an eighth of a circle `eighths` times, clockwise.
+    fn turn(self, eighths: usize) -> Self {
+        let index = Direction::ALL
+            .iter()
+            .position(|&direction| direction == self)
+            .expect("Every direction is listed");
+        return Direction::ALL[(index + eighths) % 8];
+    }
+
+    // Turn right by 90 degrees.
+    pub fn clockwise(self) -> Self {
+        return self.turn(2);
+    }
+
+    // Turn left by 90 degrees.
+    pub fn counter_clockwise(self) -> Self {
+        return self.turn(6);
+    }
+
+    pub fn reverse(self) -> Self {
+        return self.turn(4);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns_around() {
+        assert_eq!(Direction::Up.clockwise(), Direction::Right);
+        assert_eq!(Direction::Up.counter_clockwise(), Direction::Left);
+        assert_eq!(Direction::UpLeft.clockwise(), Direction::UpRight);
+        assert_eq!(Direction::DownRight.reverse(), Direction::UpLeft);
+
+        for direction in Direction::ALL {
+            assert_eq!(direction.clockwise().counter_clockwise(), direction);
+            assert_eq!(direction.reverse().offset(), -direction.offset());
+        }
+    }
+
+    #[test]
+    fn parses_arrows_and_compass_points() {
+        let arrows: Vec<Direction> = "^>v<".chars().filter_map(Direction::from_arrow).collect();
+        assert_eq!(arrows, Direction::CARDINAL);
+
+        let compass: Vec<Direction> = "NESW".chars().filter_map(Direction::from_compass).collect();
+        assert_eq!(compass, Direction::CARDINAL);
+
+        assert_eq!(Direction::from_arrow('x'), None);
+        assert_eq!(Direction::Left.arrow(), '<');
+    }
+}
@@ -1,13 +1,182 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use crate::error::{self, Error, Line};
+use std::fmt;
+use std::str::FromStr;
 
-// The output is wrapped in a Result to allow matching on errors.
-// Returns an Iterator to the Reader of the lines of the file.
-pub fn read<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+// Split the input into the groups of lines between blank lines. A run of blank lines
+// counts as one separator, and blank lines at the start or end don't make empty
+// sections.
+pub fn sections(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut sections = vec![];
+    let mut section = vec![];
+
+    for line in error::numbered(input) {
+        if !line.text.is_empty() {
+            section.push(line);
+        } else if !section.is_empty() {
+            sections.push(std::mem::take(&mut section));
+        }
+    }
+    if !section.is_empty() {
+        sections.push(section);
+    }
+
+    return sections;
+}
+
+// Split an input made of `first`, a blank line and then `second`.
+pub fn two_sections<'a>(
+    input: &'a str,
+    first: &str,
+    second: &str,
+) -> Result<(Vec<Line<'a>>, Vec<Line<'a>>), Error> {
+    let mut sections = sections(input).into_iter();
+
+    let (Some(first_lines), Some(second_lines)) = (sections.next(), sections.next()) else {
+        return Err(Error::Input(format!(
+            "expected a blank line between {first} and {second}"
+        )));
+    };
+    if let Some(extra) = sections.next() {
+        return Err(extra[0].error(format!("expected the input to end after {second}")));
+    }
+
+    return Ok((first_lines, second_lines));
+}
+
+// Every integer in the line, ignoring whatever text is around them. A '-' directly in
+// front of the digits makes the integer negative.
+pub fn ints<T>(line: &Line) -> Result<Vec<T>, Error>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let text = line.text;
+    let bytes = text.as_bytes();
+
+    let mut ints = vec![];
+    let mut index = 0;
+    while index < bytes.len() {
+        let negative = bytes[index] == b'-' && bytes.get(index + 1).is_some_and(u8::is_ascii_digit);
+        if !negative && !bytes[index].is_ascii_digit() {
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        index += 1;
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+        }
+        ints.push(line.parse::<T>(&text[start..index])?);
+    }
+
+    return Ok(ints);
+}
+
+// Exactly `N` integers from the line, as `ints` finds them. `what` describes them for
+// the error when there are more or fewer.
+pub fn ints_array<T, const N: usize>(line: &Line, what: &str) -> Result<[T; N], Error>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let ints = ints::<T>(line)?;
+    let count = ints.len();
+    return ints
+        .try_into()
+        .map_err(|_| line.error(format!("expected {what} but found {count} numbers")));
+}
+
+// Parse every item of a list separated by `delimiter`. An item that doesn't parse is
+// reported at its column.
+pub fn list<T>(line: &Line, delimiter: &str) -> Result<Vec<T>, Error>
 where
-    P: AsRef<Path>,
+    T: FromStr,
+    T::Err: fmt::Display,
 {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    return line
+        .text
+        .split(delimiter)
+        .map(|item| line.parse::<T>(item))
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> Line<'_> {
+        return Line { number: 1, text };
+    }
+
+    #[test]
+    fn splits_sections_at_blank_lines() {
+        let split = sections("\na\nb\n\n\nc\n\n");
+        let texts: Vec<Vec<&str>> = split
+            .iter()
+            .map(|section| section.iter().map(|line| line.text).collect())
+            .collect();
+        assert_eq!(texts, [vec!["a", "b"], vec!["c"]]);
+        assert_eq!(split[1][0].number, 6);
+
+        let windows = sections("a\r\n\r\nb\r\n");
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[1][0].text, "b");
+    }
+
+    #[test]
+    fn requires_two_sections() {
+        let error = two_sections("a\nb\n", "the rules", "the updates");
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "expected a blank line between the rules and the updates"
+        );
+
+        let error = two_sections("a\n\nb\n\nc\n", "the rules", "the updates");
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "line 5: expected the input to end after the updates"
+        );
+    }
+
+    #[test]
+    fn finds_signed_integers_anywhere() {
+        assert_eq!(ints::<i64>(&line("p=0,4 v=3,-3")).unwrap(), [0, 4, 3, -3]);
+        assert_eq!(
+            ints::<i32>(&line("Button A: X+94, Y-34")).unwrap(),
+            [94, -34]
+        );
+        assert_eq!(ints::<u8>(&line("no numbers - here")).unwrap(), []);
+
+        let error = ints::<u8>(&line("x 300")).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("line 1, column 3: invalid value '300'"));
+
+        let error = ints_array::<i32, 2>(&line("1 2 3"), "an X and a Y value").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1: expected an X and a Y value but found 3 numbers"
+        );
+    }
+
+    #[test]
+    fn parses_delimited_lists() {
+        assert_eq!(list::<u32>(&line("75,47,61"), ",").unwrap(), [75, 47, 61]);
+        assert_eq!(
+            list::<String>(&line("r, wr, b"), ", ").unwrap(),
+            ["r", "wr", "b"]
+        );
+
+        let error = list::<u32>(&line("75,x"), ",").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("line 1, column 4: invalid value 'x'"));
+
+        let error = list::<char>(&line("a,bc"), ",").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 3: invalid value 'bc': too many characters in string"
+        );
+    }
 }
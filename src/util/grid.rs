@@ -1,10 +1,13 @@
-use crate::util::point::Point;
+use crate::error::{self, Error, Line};
+use crate::util::direction::Direction;
+use crate::util::point::{Point, Vector};
 use std::{
     fmt,
     ops::{Index, IndexMut},
+    str::FromStr,
 };
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct Grid<T> {
     pub col_count: usize,
     pub row_count: usize,
@@ -12,6 +15,186 @@ pub struct Grid<T> {
 }
 
 impl<T> Grid<T> {
+    // Build a grid of `row_count` rows and `col_count` columns where every cell is made
+    // by `cell` from its point, row by row.
+    pub fn from_fn<F>(row_count: usize, col_count: usize, mut cell: F) -> Self
+    where
+        F: FnMut(Point) -> T,
+    {
+        let contents = (0..row_count)
+            .flat_map(|row| (0..col_count).map(move |col| Point::new(col, row)))
+            .map(&mut cell)
+            .collect();
+
+        return Grid {
+            col_count,
+            row_count,
+            contents,
+        };
+    }
+
+    // Build a grid with one row per line, turning every character into a cell with `cell`.
+    // A character `cell` rejects is reported with its message at its column, as is a row
+    // that's longer or shorter than the first one.
+    pub fn from_lines<'a, I, F, E>(lines: I, mut cell: F) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = Line<'a>>,
+        F: FnMut(char) -> Result<T, E>,
+        E: Into<String>,
+    {
+        let mut contents = vec![];
+        let mut col_count = 0;
+        let mut row_count = 0;
+
+        for line in lines {
+            let row_start = contents.len();
+            for (index, char) in line.text.chars().enumerate() {
+                contents.push(cell(char).map_err(|message| line.error_at_char(index, message))?);
+            }
+
+            let row_length = contents.len() - row_start;
+            if row_count == 0 {
+                col_count = row_length;
+            } else if row_length != col_count {
+                return Err(line.error(format!("expected {col_count} cells but got {row_length}")));
+            }
+            row_count += 1;
+        }
+
+        if row_count == 0 || col_count == 0 {
+            return Err(Error::Input(
+                "expected a grid but the input is empty".to_string(),
+            ));
+        }
+
+        return Ok(Grid {
+            col_count,
+            row_count,
+            contents,
+        });
+    }
+
+    // Build a grid from text, such as a puzzle example, with one row per line.
+    pub fn parse<F, E>(text: &str, cell: F) -> Result<Self, Error>
+    where
+        F: FnMut(char) -> Result<T, E>,
+        E: Into<String>,
+    {
+        return Grid::from_lines(error::numbered(text), cell);
+    }
+
+    // A grid of the same shape with `f` applied to every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        return Grid {
+            col_count: self.col_count,
+            row_count: self.row_count,
+            contents: self.contents.iter().map(f).collect(),
+        };
+    }
+
+    // Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let col_count = self.col_count;
+        return (0..self.row_count)
+            .flat_map(move |row| (0..col_count).map(move |col| Point::new(col, row)));
+    }
+
+    // Every cell along with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        return self.points().zip(self.contents.iter());
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        return self.points().zip(self.contents.iter_mut());
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        return point.x < self.col_count && point.y < self.row_count;
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        if !self.contains(point) {
+            return None;
+        }
+        return Some(&self[point]);
+    }
+
+    // The cell one `offset` away from `point`, if it's inside the grid.
+    pub fn neighbor(&self, point: Point, offset: Vector) -> Option<(Point, &T)> {
+        let neighbor = point.checked_offset(offset)?;
+        return Some((neighbor, self.get(neighbor)?));
+    }
+
+    // The neighboring point in `direction`, if it's inside the grid.
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        return point.step(direction).filter(|&next| self.contains(next));
+    }
+
+    // The cells that share an edge with `point` and are inside the grid.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        return Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |direction| self.neighbor(point, direction.offset()));
+    }
+
+    // The cells that share an edge or a corner with `point` and are inside the grid.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        return Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.neighbor(point, direction.offset()));
+    }
+
+    // The cells met walking from `start` in steps of `offset` until leaving the grid,
+    // not including `start` itself.
+    pub fn ray(&self, start: Point, offset: Vector) -> impl Iterator<Item = (Point, &T)> {
+        return std::iter::successors(self.neighbor(start, offset), move |&(point, _)| {
+            return self.neighbor(point, offset);
+        });
+    }
+
+    // The point `position` lands on when the grid repeats forever in every direction,
+    // so leaving one edge comes back in at the opposite one.
+    pub fn wrap(&self, position: Vector) -> Point {
+        let size = Vector::new(self.col_count as isize, self.row_count as isize);
+        return Point::try_from(position.rem_euclid(size)).expect("Wrapped points aren't negative");
+    }
+
+    pub fn get_wrapping(&self, position: Vector) -> &T {
+        return &self[self.wrap(position)];
+    }
+
+    // The cell one `offset` away from `point`, wrapping around the edges of the grid.
+    pub fn neighbor_wrapping(&self, point: Point, offset: Vector) -> (Point, &T) {
+        let position = Vector::try_from(point).expect("The grid must fit in memory") + offset;
+        let neighbor = self.wrap(position);
+        return (neighbor, &self[neighbor]);
+    }
+
+    // The cells of every row, top to bottom. A grid without columns still has its
+    // rows, they're just empty.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        return (0..self.row_count).map(|row| self.row(row));
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        let start = row * self.col_count;
+        return &self.contents[start..start + self.col_count];
+    }
+
+    // The cells of column `col`, top to bottom.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.col_count, "Column {col} is outside the grid");
+        return self.contents[col..].iter().step_by(self.col_count);
+    }
+
+    // The cells of every column, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        return (0..self.col_count).map(|col| self.column(col));
+    }
+
     pub fn checked_get(&self, row: &Option<usize>, col: &Option<usize>) -> Option<&T> {
         if row.is_none() || col.is_none() {
             return None;
@@ -46,16 +229,89 @@ impl<T> Grid<T> {
     }
 }
 
-impl<T: fmt::Display> Grid<T> {
-    pub fn print(&self) {
-        for row in 0..self.row_count {
-            for col in 0..self.col_count {
-                let point = Point::new(col, row);
-                print!("{}", self[point]);
+impl<T: Clone> Grid<T> {
+    // Build a grid of `row_count` rows and `col_count` columns with `value` in every cell.
+    pub fn new_filled(row_count: usize, col_count: usize, value: T) -> Self {
+        return Grid {
+            col_count,
+            row_count,
+            contents: vec![value; row_count * col_count],
+        };
+    }
+
+    // A new grid of `col_count` by `row_count` cells where the cell at each point is
+    // copied from the point `source` maps it to.
+    fn remap<F>(&self, col_count: usize, row_count: usize, source: F) -> Self
+    where
+        F: Fn(Point) -> Point,
+    {
+        return Grid::from_fn(row_count, col_count, |point| self[source(point)].clone());
+    }
+
+    // Mirror the grid across its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self {
+        return self.remap(self.row_count, self.col_count, |point| {
+            return Point::new(point.y, point.x);
+        });
+    }
+
+    // Mirror the grid left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        return self.remap(self.col_count, self.row_count, |point| {
+            return Point::new(self.col_count - 1 - point.x, point.y);
+        });
+    }
+
+    // Mirror the grid top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        return self.remap(self.col_count, self.row_count, |point| {
+            return Point::new(point.x, self.row_count - 1 - point.y);
+        });
+    }
+
+    // Turn the grid a quarter turn clockwise, so the first column becomes the first row.
+    pub fn rotate_90(&self) -> Self {
+        return self.remap(self.row_count, self.col_count, |point| {
+            return Point::new(point.y, self.row_count - 1 - point.x);
+        });
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        return self.remap(self.col_count, self.row_count, |point| {
+            return Point::new(self.col_count - 1 - point.x, self.row_count - 1 - point.y);
+        });
+    }
+
+    // Turn the grid a quarter turn counter-clockwise.
+    pub fn rotate_270(&self) -> Self {
+        return self.remap(self.row_count, self.col_count, |point| {
+            return Point::new(self.col_count - 1 - point.y, point.x);
+        });
+    }
+}
+
+// Draw the grid one row per line, with the cells of a row side by side.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
             }
-            println!();
+            writeln!(f)?;
         }
-        println!();
+        return Ok(());
+    }
+}
+
+// Show the cells split into their rows rather than as one long list.
+impl<T: fmt::Debug> fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f
+            .debug_struct("Grid")
+            .field("col_count", &self.col_count)
+            .field("row_count", &self.row_count)
+            .field("rows", &self.rows().collect::<Vec<&[T]>>())
+            .finish();
     }
 }
 
@@ -71,3 +327,171 @@ impl<T> IndexMut<Point> for Grid<T> {
         return &mut self.contents[self.col_count * point.y + point.x];
     }
 }
+
+impl Grid<char> {
+    // Build a grid of the characters of each line as they are.
+    pub fn from_chars<'a, I>(lines: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = Line<'a>>,
+    {
+        return Grid::from_lines(lines, Ok::<char, String>);
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        return Grid::from_chars(error::numbered(text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rows_in_order() {
+        let grid: Grid<char> = "ab\ncd\nef\n".parse().unwrap();
+        assert_eq!((grid.row_count, grid.col_count), (3, 2));
+        assert_eq!(grid[Point::new(1, 2)], 'f');
+    }
+
+    #[test]
+    fn maps_every_cell() {
+        let grid = Grid::parse("12\n34\n", |char| char.to_digit(10).ok_or("not a digit")).unwrap();
+        assert_eq!(grid.contents, [1, 2, 3, 4]);
+
+        let error = Grid::parse("12\n3x\n", |char| char.to_digit(10).ok_or("not a digit"));
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "line 2, column 2: not a digit"
+        );
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let error = "...\n..\n".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected 3 cells but got 2");
+    }
+
+    #[test]
+    fn neighbors_stay_inside_the_grid() {
+        let grid: Grid<char> = "abc\ndef\nghi\n".parse().unwrap();
+        let cells = |neighbors: Vec<(Point, &char)>| -> String {
+            return neighbors.into_iter().map(|(_, &char)| char).collect();
+        };
+
+        assert_eq!(cells(grid.neighbors4(Point::new(1, 1)).collect()), "bfhd");
+        assert_eq!(cells(grid.neighbors4(Point::new(0, 0)).collect()), "bd");
+        assert_eq!(
+            cells(grid.neighbors8(Point::new(1, 1)).collect()),
+            "bcfihgda"
+        );
+        assert_eq!(cells(grid.neighbors8(Point::new(2, 2)).collect()), "fhe");
+    }
+
+    #[test]
+    fn rays_run_to_the_edge() {
+        let grid: Grid<char> = "abc\ndef\nghi\n".parse().unwrap();
+        let ray: Vec<(Point, &char)> = grid.ray(Point::new(0, 0), Vector::new(1, 1)).collect();
+        assert_eq!(ray, [(Point::new(1, 1), &'e'), (Point::new(2, 2), &'i')]);
+        assert_eq!(
+            grid.ray(Point::new(0, 1), Direction::Left.offset()).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn steps_stop_at_the_edge() {
+        let grid: Grid<char> = "ab\ncd\n".parse().unwrap();
+        assert_eq!(
+            grid.step(Point::new(0, 0), Direction::Right),
+            Some(Point::new(1, 0))
+        );
+        assert_eq!(grid.step(Point::new(1, 0), Direction::Right), None);
+        assert_eq!(grid.step(Point::new(0, 0), Direction::Up), None);
+    }
+
+    #[test]
+    fn wraps_around_the_edges() {
+        let grid: Grid<char> = "abc\ndef\n".parse().unwrap();
+        assert_eq!(grid.wrap(Vector::new(-1, -1)), Point::new(2, 1));
+        assert_eq!(grid.get_wrapping(Vector::new(7, 4)), &'b');
+        assert_eq!(
+            grid.neighbor_wrapping(Point::new(2, 0), Direction::Right.offset()),
+            (Point::new(0, 0), &'a')
+        );
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid: Grid<char> = "abc\ndef\n".parse().unwrap();
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, ["abc", "def"]);
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+
+        let empty = Grid::new_filled(2, 0, 'x');
+        assert_eq!(empty.rows().count(), 2);
+        assert_eq!(empty.to_string(), "\n\n");
+        assert_eq!(Grid::new_filled(0, 3, 'x').to_string(), "");
+    }
+
+    #[test]
+    fn transforms() {
+        let grid: Grid<char> = "abc\ndef\n".parse().unwrap();
+        let text = |grid: Grid<char>| -> Vec<String> {
+            return grid.rows().map(|row| row.iter().collect()).collect();
+        };
+
+        assert_eq!(text(grid.transpose()), ["ad", "be", "cf"]);
+        assert_eq!(text(grid.flip_horizontal()), ["cba", "fed"]);
+        assert_eq!(text(grid.flip_vertical()), ["def", "abc"]);
+        assert_eq!(text(grid.rotate_90()), ["da", "eb", "fc"]);
+        assert_eq!(text(grid.rotate_180()), ["fed", "cba"]);
+        assert_eq!(text(grid.rotate_270()), ["cf", "be", "ad"]);
+
+        assert_eq!(grid.rotate_90().rotate_270(), grid);
+        assert_eq!(grid.rotate_90().rotate_90(), grid.rotate_180());
+        assert_eq!(grid.transpose().flip_horizontal(), grid.rotate_90());
+    }
+
+    #[test]
+    fn builds_and_maps_cells() {
+        let grid = Grid::from_fn(2, 3, |point| point.x + 10 * point.y);
+        assert_eq!((grid.row_count, grid.col_count), (2, 3));
+        assert_eq!(grid.contents, [0, 1, 2, 10, 11, 12]);
+
+        let odd = grid.map(|value| value % 2 == 1);
+        assert_eq!(odd.contents, [false, true, false, false, true, false]);
+
+        let filled = Grid::new_filled(2, 2, 'x');
+        assert_eq!(filled.to_string(), "xx\nxx\n");
+    }
+
+    #[test]
+    fn enumerates_points() {
+        let mut grid: Grid<char> = "ab\ncd\n".parse().unwrap();
+        let cells: Vec<(Point, &char)> = grid.iter().collect();
+        assert_eq!(cells[2], (Point::new(0, 1), &'c'));
+
+        for (point, cell) in grid.iter_mut() {
+            if point.x == point.y {
+                *cell = '#';
+            }
+        }
+        assert_eq!(grid.to_string(), "#b\nc#\n");
+        assert_eq!(
+            format!("{grid:?}"),
+            "Grid { col_count: 2, row_count: 2, rows: [['#', 'b'], ['c', '#']] }"
+        );
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert!(matches!("".parse::<Grid<char>>(), Err(Error::Input(_))));
+    }
+}
@@ -0,0 +1,391 @@
+use crate::util::grid::Grid;
+use crate::util::point::Point;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const RED: Color = Color::rgb(220, 50, 47);
+    pub const GREEN: Color = Color::rgb(133, 153, 0);
+    pub const BLUE: Color = Color::rgb(38, 139, 210);
+    pub const GRAY: Color = Color::rgb(128, 128, 128);
+
+    #[inline]
+    #[must_use]
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b }
+    }
+
+    // A color for each of any number of labels, such as regions, that neighboring
+    // labels are unlikely to share.
+    pub fn from_label(label: usize) -> Self {
+        // Step around the hue circle by the golden angle.
+        let hue = (label as f64 * 137.507_764) % 360.0;
+        let sector = hue / 60.0;
+        let rising = (255.0 * (1.0 - (sector % 2.0 - 1.0).abs())) as u8;
+        return match sector as u8 {
+            0 => Color::rgb(255, rising, 0),
+            1 => Color::rgb(rising, 255, 0),
+            2 => Color::rgb(0, 255, rising),
+            3 => Color::rgb(0, rising, 255),
+            4 => Color::rgb(rising, 0, 255),
+            _ => Color::rgb(255, 0, rising),
+        };
+    }
+}
+
+// Written as hex, such as "#ff8000".
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b);
+    }
+}
+
+// A path drawn on top of a rendered grid, such as the best route through a maze.
+#[derive(Clone, Copy, Debug)]
+pub struct Overlay<'a> {
+    pub path: &'a [Point],
+    pub color: Color,
+}
+
+// A grid of pixels, one per cell of the grid it was rendered from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub pixels: Grid<Color>,
+}
+
+impl<T> Grid<T> {
+    // Render the grid with one pixel per cell, colored by `color`.
+    pub fn to_image<F>(&self, color: F, overlay: Option<Overlay>) -> Image
+    where
+        F: Fn(&T) -> Color,
+    {
+        let mut pixels = self.map(color);
+
+        if let Some(overlay) = overlay {
+            for &point in overlay.path {
+                if pixels.contains(point) {
+                    pixels[point] = overlay.color;
+                }
+            }
+        }
+
+        return Image { pixels };
+    }
+
+    // Render the grid as an SVG drawing with a square per cell, colored by `color`,
+    // and the overlay as a line through the middle of its cells.
+    pub fn to_svg<F>(&self, color: F, overlay: Option<Overlay>) -> String
+    where
+        F: Fn(&T) -> Color,
+    {
+        const CELL_SIZE: usize = 10;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" width=\"{}\" height=\"{}\" shape-rendering=\"crispEdges\">\n",
+            self.col_count,
+            self.row_count,
+            self.col_count * CELL_SIZE,
+            self.row_count * CELL_SIZE
+        );
+
+        for (point, cell) in self.iter() {
+            let fill = color(cell);
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{fill}\"/>\n",
+                point.x, point.y
+            ));
+        }
+
+        if let Some(overlay) = overlay {
+            let points: Vec<String> = overlay
+                .path
+                .iter()
+                .map(|point| format!("{}.5,{}.5", point.x, point.y))
+                .collect();
+            svg.push_str(&format!(
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.3\" stroke-linejoin=\"round\" shape-rendering=\"auto\"/>\n",
+                points.join(" "),
+                overlay.color
+            ));
+        }
+
+        svg.push_str("</svg>\n");
+        return svg;
+    }
+}
+
+impl Image {
+    // Blow every pixel up into a `factor` by `factor` square, to make small grids
+    // easier to look at.
+    pub fn scaled(&self, factor: usize) -> Image {
+        return Image {
+            pixels: Grid::from_fn(
+                self.pixels.row_count * factor,
+                self.pixels.col_count * factor,
+                |point| self.pixels[Point::new(point.x / factor, point.y / factor)],
+            ),
+        };
+    }
+
+    fn rgb_bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        return self
+            .pixels
+            .contents
+            .iter()
+            .flat_map(|color| [color.r, color.g, color.b]);
+    }
+
+    // Encode as a binary PPM, the simplest format most image viewers can open.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!(
+            "P6\n{} {}\n255\n",
+            self.pixels.col_count, self.pixels.row_count
+        )
+        .into_bytes();
+        ppm.extend(self.rgb_bytes());
+        return ppm;
+    }
+
+    // Encode as a PNG. The image data isn't compressed, which keeps the encoder
+    // small at the cost of larger files.
+    pub fn to_png(&self) -> Vec<u8> {
+        let width = self.pixels.col_count as u32;
+        let height = self.pixels.row_count as u32;
+
+        let mut header = vec![];
+        header.extend(width.to_be_bytes());
+        header.extend(height.to_be_bytes());
+        // 8 bits per channel, RGB, default compression, filtering and no interlacing.
+        header.extend([8, 2, 0, 0, 0]);
+
+        // Every row starts with the filter it's encoded with, none here.
+        let row_length = self.pixels.col_count * 3;
+        let rgb: Vec<u8> = self.rgb_bytes().collect();
+        let mut raw = Vec::with_capacity((row_length + 1) * self.pixels.row_count);
+        for row in rgb.chunks(row_length.max(1)) {
+            raw.push(0);
+            raw.extend(row);
+        }
+
+        let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut png, b"IEND", &[]);
+        return png;
+    }
+
+    // Write the image in the format the extension of `path` asks for, "png" or "ppm".
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let bytes = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("png") => self.to_png(),
+            Some("ppm") => self.to_ppm(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} should end in .png or .ppm", path.display()),
+                ));
+            }
+        };
+        return fs::write(path, bytes);
+    }
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+// Wrap `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = u16::MAX as usize;
+
+    // Deflate with a 32K window and no preset dictionary.
+    let mut stream = vec![0x78, 0x01];
+
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        // An empty final block.
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        let length = block.len() as u16;
+        stream.push(is_final as u8);
+        stream.extend(length.to_le_bytes());
+        stream.extend((!length).to_le_bytes());
+        stream.extend(block);
+    }
+
+    stream.extend(adler32(data).to_be_bytes());
+    return stream;
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb88320 & mask);
+        }
+    }
+    return !crc;
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MODULUS: u32 = 65521;
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % MODULUS;
+        b = (b + a) % MODULUS;
+    }
+    return (b << 16) | a;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Grid<bool> {
+        return Grid {
+            col_count: 3,
+            row_count: 2,
+            contents: vec![true, false, true, false, true, false],
+        };
+    }
+
+    fn black_and_white(&cell: &bool) -> Color {
+        return if cell { Color::BLACK } else { Color::WHITE };
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+    }
+
+    #[test]
+    fn overlays_a_path() {
+        let path = [Point::new(1, 0), Point::new(1, 1), Point::new(5, 5)];
+        let overlay = Overlay {
+            path: &path,
+            color: Color::RED,
+        };
+        let image = checkerboard().to_image(black_and_white, Some(overlay));
+        assert_eq!(
+            image.pixels.contents,
+            [
+                Color::BLACK,
+                Color::RED,
+                Color::BLACK,
+                Color::WHITE,
+                Color::RED,
+                Color::WHITE
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_ppm() {
+        let ppm = checkerboard().to_image(black_and_white, None).to_ppm();
+        assert!(ppm.starts_with(b"P6\n3 2\n255\n"));
+        assert_eq!(ppm.len(), 11 + 3 * 2 * 3);
+        assert_eq!(ppm[11..14], [0, 0, 0]);
+        assert_eq!(ppm[14..17], [255, 255, 255]);
+    }
+
+    #[test]
+    fn encodes_png() {
+        let png = checkerboard()
+            .to_image(black_and_white, None)
+            .scaled(2)
+            .to_png();
+        assert_eq!(
+            png[..8],
+            [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']
+        );
+
+        // Walk the chunks, checking each checksum and collecting the image data.
+        let mut offset = 8;
+        let mut kinds = vec![];
+        let mut image_data = vec![];
+        while offset < png.len() {
+            let length = u32::from_be_bytes(png[offset..offset + 4].try_into().unwrap()) as usize;
+            let body = &png[offset + 4..offset + 8 + length];
+            let crc = u32::from_be_bytes(
+                png[offset + 8 + length..offset + 12 + length]
+                    .try_into()
+                    .unwrap(),
+            );
+            assert_eq!(crc32(body), crc);
+
+            kinds.push(String::from_utf8(body[..4].to_vec()).unwrap());
+            if &body[..4] == b"IHDR" {
+                assert_eq!(body[4..12], [0, 0, 0, 6, 0, 0, 0, 4]);
+            }
+            if &body[..4] == b"IDAT" {
+                image_data.extend(&body[4..]);
+            }
+            offset += 12 + length;
+        }
+        assert_eq!(kinds, ["IHDR", "IDAT", "IEND"]);
+
+        // A single stored block holding 4 rows of a filter byte and 6 pixels.
+        let raw = &image_data[7..image_data.len() - 4];
+        assert_eq!(image_data[2], 1);
+        assert_eq!(raw.len(), 4 * (1 + 6 * 3));
+        assert_eq!(raw[..7], [0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            image_data[image_data.len() - 4..],
+            adler32(raw).to_be_bytes()
+        );
+    }
+
+    #[test]
+    fn splits_large_images_into_blocks() {
+        let stream = zlib_stored(&vec![7; 70_000]);
+        // Header, two blocks with 5 bytes of framing each and the checksum.
+        assert_eq!(stream.len(), 2 + 5 + 65_535 + 5 + 4_465 + 4);
+        assert_eq!(stream[2], 0);
+        assert_eq!(stream[2 + 5 + 65_535], 1);
+    }
+
+    #[test]
+    fn renders_svg() {
+        let path = [Point::new(0, 0), Point::new(2, 1)];
+        let overlay = Overlay {
+            path: &path,
+            color: Color::RED,
+        };
+        let svg = checkerboard().to_svg(black_and_white, Some(overlay));
+        assert!(svg.starts_with("<svg "));
+        assert_eq!(svg.matches("<rect ").count(), 6);
+        assert!(svg.contains("<rect x=\"1\" y=\"0\" width=\"1\" height=\"1\" fill=\"#ffffff\"/>"));
+        assert!(svg.contains("points=\"0.5,0.5 2.5,1.5\""));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn label_colors_differ() {
+        let colors: Vec<Color> = (0..6).map(Color::from_label).collect();
+        for (index, color) in colors.iter().enumerate() {
+            assert!(!colors[index + 1..].contains(color));
+        }
+    }
+}
@@ -1,3 +1,7 @@
+use crate::util::direction::Direction;
+use std::num::TryFromIntError;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
 #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
 pub struct Point {
     pub x: usize,
@@ -10,4 +14,165 @@ impl Point {
     pub const fn new(x: usize, y: usize) -> Self {
         Point { x, y }
     }
+
+    // The point `offset` away, or `None` if that's left of or above the origin.
+    #[inline]
+    #[must_use]
+    pub fn checked_offset(self, offset: Vector) -> Option<Self> {
+        return Some(Point {
+            x: self.x.checked_add_signed(offset.x)?,
+            y: self.y.checked_add_signed(offset.y)?,
+        });
+    }
+
+    // The neighboring point in `direction`, or `None` if that's left of or above the
+    // origin.
+    #[inline]
+    #[must_use]
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        return self.checked_offset(direction.offset());
+    }
+
+    // The number of steps between the points moving only along rows and columns.
+    pub fn manhattan(self, other: Point) -> usize {
+        return self.x.abs_diff(other.x) + self.y.abs_diff(other.y);
+    }
+}
+
+// A signed position or step between positions, with `y` growing downwards.
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq)]
+pub struct Vector {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector::new(0, 0);
+
+    #[inline]
+    #[must_use]
+    pub const fn new(x: isize, y: isize) -> Self {
+        Vector { x, y }
+    }
+
+    // The number of steps between the vectors moving only along rows and columns.
+    pub fn manhattan(self, other: Vector) -> usize {
+        return self.x.abs_diff(other.x) + self.y.abs_diff(other.y);
+    }
+
+    // The number of steps between the vectors when diagonal steps are allowed too.
+    pub fn chebyshev(self, other: Vector) -> usize {
+        return self.x.abs_diff(other.x).max(self.y.abs_diff(other.y));
+    }
+
+    // Wrap the vector into the area from the origin up to, but not including, `bounds`.
+    pub fn rem_euclid(self, bounds: Vector) -> Self {
+        return Vector {
+            x: self.x.rem_euclid(bounds.x),
+            y: self.y.rem_euclid(bounds.y),
+        };
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        return Vector::new(self.x + other.x, self.y + other.y);
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, other: Vector) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        return Vector::new(self.x - other.x, self.y - other.y);
+    }
+}
+
+impl SubAssign for Vector {
+    fn sub_assign(&mut self, other: Vector) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Vector;
+
+    fn mul(self, scalar: isize) -> Vector {
+        return Vector::new(self.x * scalar, self.y * scalar);
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        return Vector::new(-self.x, -self.y);
+    }
+}
+
+impl TryFrom<Point> for Vector {
+    type Error = TryFromIntError;
+
+    fn try_from(point: Point) -> Result<Self, Self::Error> {
+        return Ok(Vector::new(point.x.try_into()?, point.y.try_into()?));
+    }
+}
+
+// Fails for vectors left of or above the origin.
+impl TryFrom<Vector> for Point {
+    type Error = TryFromIntError;
+
+    fn try_from(vector: Vector) -> Result<Self, Self::Error> {
+        return Ok(Point::new(vector.x.try_into()?, vector.y.try_into()?));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vector_arithmetic() {
+        let a = Vector::new(3, -2);
+        let b = Vector::new(-1, 4);
+        assert_eq!(a + b, Vector::new(2, 2));
+        assert_eq!(a - b, Vector::new(4, -6));
+        assert_eq!(a * 3, Vector::new(9, -6));
+        assert_eq!(-a, Vector::new(-3, 2));
+        assert_eq!(
+            Vector::new(-1, 7).rem_euclid(Vector::new(5, 5)),
+            Vector::new(4, 2)
+        );
+    }
+
+    #[test]
+    fn distances() {
+        let a = Vector::new(3, -2);
+        let b = Vector::new(-1, 4);
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(Point::new(1, 5).manhattan(Point::new(4, 2)), 6);
+    }
+
+    #[test]
+    fn converts_between_points_and_vectors() {
+        let point = Point::new(2, 7);
+        assert_eq!(Vector::try_from(point), Ok(Vector::new(2, 7)));
+        assert_eq!(Point::try_from(Vector::new(2, 7)), Ok(point));
+        assert!(Point::try_from(Vector::new(-1, 0)).is_err());
+
+        assert_eq!(
+            point.checked_offset(Vector::new(-2, 1)),
+            Some(Point::new(0, 8))
+        );
+        assert_eq!(point.checked_offset(Vector::new(-3, 0)), None);
+    }
 }
@@ -0,0 +1,81 @@
+// A small, fast pseudo-random number generator (SplitMix64) for generating test inputs.
+// The same seed always produces the same numbers, so failures can be reproduced. It's
+// not suitable for anything that needs unpredictable numbers.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        return Rng { state: seed };
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut mixed = self.state;
+        mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        return mixed ^ (mixed >> 31);
+    }
+
+    // A number from 0 up to, but not including, `bound`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "The bound must be positive");
+        // Multiplying keeps the bias negligible for the small bounds inputs need.
+        return ((self.next_u64() as u128 * bound as u128) >> 64) as usize;
+    }
+
+    // A number from `low` to `high`, both included.
+    pub fn between(&mut self, low: usize, high: usize) -> usize {
+        return low + self.below(high - low + 1);
+    }
+
+    // True one time in `odds`.
+    pub fn one_in(&mut self, odds: usize) -> bool {
+        return self.below(odds) == 0;
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        return &items[self.below(items.len())];
+    }
+
+    // Put the items in a random order.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.below(index + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeds_repeat() {
+        let mut first = Rng::new(42);
+        let mut second = Rng::new(42);
+        for _ in 0..5 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+        assert_ne!(Rng::new(43).next_u64(), Rng::new(42).next_u64());
+    }
+
+    #[test]
+    fn stays_in_bounds() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let value = rng.between(3, 8);
+            assert!((3..=8).contains(&value));
+            seen[value - 3] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+
+        let mut items: Vec<usize> = (0..20).collect();
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<usize>>());
+    }
+}
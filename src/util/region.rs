@@ -0,0 +1,142 @@
+use crate::util::direction::Direction;
+use crate::util::grid::Grid;
+use crate::util::point::{Point, Vector};
+
+// A group of cells with equal values connected through their edges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub cells: Vec<Point>,
+    // The number of cell edges on the border of the region.
+    pub perimeter: usize,
+    // The number of straight sections the border is made of.
+    pub sides: usize,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        return self.cells.len();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Regions {
+    // The index in `regions` of the region every cell belongs to.
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl<T: PartialEq> Grid<T> {
+    // Split the grid into regions of connected cells with equal values. Regions are
+    // numbered in the order their first cell appears, row by row.
+    pub fn regions(&self) -> Regions {
+        let mut labels: Grid<Option<usize>> =
+            Grid::new_filled(self.row_count, self.col_count, None);
+        let mut regions = vec![];
+
+        for row in 0..self.row_count {
+            for col in 0..self.col_count {
+                let point = Point::new(col, row);
+                if labels[point].is_some() {
+                    continue;
+                }
+
+                let label = regions.len();
+                labels[point] = Some(label);
+                regions.push(self.fill_region(point, label, &mut labels));
+            }
+        }
+
+        return Regions {
+            labels: labels.map(|label| label.expect("Every cell is in a region")),
+            regions,
+        };
+    }
+
+    // Walk the region that contains `start` with an explicit stack, so large regions
+    // can't overflow the call stack.
+    fn fill_region(&self, start: Point, label: usize, labels: &mut Grid<Option<usize>>) -> Region {
+        let value = &self[start];
+        let is_same = |point: Point, offset: Vector| {
+            return self
+                .neighbor(point, offset)
+                .is_some_and(|(_, other)| other == value);
+        };
+
+        let mut region = Region {
+            cells: vec![],
+            perimeter: 0,
+            sides: 0,
+        };
+        let mut to_visit = vec![start];
+
+        while let Some(point) = to_visit.pop() {
+            region.cells.push(point);
+
+            for direction in Direction::CARDINAL {
+                if !is_same(point, direction.offset()) {
+                    region.perimeter += 1;
+                    continue;
+                }
+
+                let next = point.step(direction).expect("The neighbor is in the grid");
+                if labels[next].is_none() {
+                    labels[next] = Some(label);
+                    to_visit.push(next);
+                }
+            }
+
+            // A region has as many sides as corners, so count the corners of each cell.
+            for direction in Direction::DIAGONAL {
+                let Vector { x: dx, y: dy } = direction.offset();
+                let vertical = is_same(point, Vector::new(0, dy));
+                let diagonal = is_same(point, Vector::new(dx, dy));
+                let horizontal = is_same(point, Vector::new(dx, 0));
+
+                let exterior_corner = !vertical && !horizontal;
+                let interior_corner = vertical && horizontal && !diagonal;
+                if exterior_corner || interior_corner {
+                    region.sides += 1;
+                }
+            }
+        }
+
+        return region;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_connected_cells() {
+        let grid: Grid<char> = "AAB\nBAB\nBBA\n".parse().unwrap();
+        let Regions { labels, regions } = grid.regions();
+
+        assert_eq!(labels.contents, [0, 0, 1, 2, 0, 1, 2, 2, 3]);
+        let areas: Vec<usize> = regions.iter().map(Region::area).collect();
+        assert_eq!(areas, [3, 2, 3, 1]);
+    }
+
+    #[test]
+    fn measures_borders() {
+        // An "E" shaped region around two strips.
+        let grid: Grid<char> = "EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE\n".parse().unwrap();
+        let Regions { regions, .. } = grid.regions();
+
+        assert_eq!(regions[0].area(), 17);
+        assert_eq!(regions[0].sides, 12);
+        assert_eq!(regions[0].perimeter, 36);
+        assert_eq!(regions[1].sides, 4);
+        assert_eq!(regions[1].perimeter, 10);
+    }
+
+    #[test]
+    fn large_regions_dont_overflow() {
+        let grid = Grid::new_filled(300, 300, '.');
+        let Regions { regions, .. } = grid.regions();
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].area(), 90_000);
+        assert_eq!(regions[0].sides, 4);
+    }
+}
@@ -0,0 +1,330 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+// What a search found: the distance to every state it reached and, for every state,
+// all of the states before it on a shortest path. The search stops once every goal
+// state at the shortest distance has been reached.
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    pub start: S,
+    pub distances: HashMap<S, u32>,
+    pub predecessors: HashMap<S, Vec<S>>,
+    // The goal states at the shortest distance, in the order they were reached.
+    pub goals: Vec<S>,
+}
+
+impl<S: Copy + Eq + Hash> Search<S> {
+    fn new(start: S) -> Self {
+        return Search {
+            start,
+            distances: HashMap::from([(start, 0)]),
+            predecessors: HashMap::new(),
+            goals: vec![],
+        };
+    }
+
+    pub fn distance(&self, state: &S) -> Option<u32> {
+        return self.distances.get(state).copied();
+    }
+
+    // The distance to the closest goal, if one was reached.
+    pub fn goal_distance(&self) -> Option<u32> {
+        return self.goals.first().and_then(|goal| self.distance(goal));
+    }
+
+    // Record that `next` can be reached from `state` with a total cost of `cost`.
+    // Returns whether `next` wasn't known to be reachable this cheaply before.
+    fn relax(&mut self, state: S, next: S, cost: u32) -> bool {
+        match self.distances.get(&next) {
+            Some(&known) if cost > known => return false,
+            Some(&known) if cost == known => {
+                self.predecessors.entry(next).or_default().push(state);
+                return false;
+            }
+            _ => {}
+        }
+
+        self.distances.insert(next, cost);
+        self.predecessors.insert(next, vec![state]);
+        return true;
+    }
+
+    // One shortest path from the start to `goal`, both included.
+    pub fn path(&self, goal: S) -> Option<Vec<S>> {
+        self.distances.get(&goal)?;
+
+        let mut path = vec![goal];
+        let mut state = goal;
+        while state != self.start {
+            state = self.predecessors[&state][0];
+            path.push(state);
+        }
+        path.reverse();
+
+        return Some(path);
+    }
+
+    // Every state that's on at least one shortest path from the start to one of `goals`.
+    pub fn on_shortest_paths(&self, goals: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut states = HashSet::new();
+        let mut to_visit: Vec<S> = goals
+            .into_iter()
+            .filter(|goal| self.distances.contains_key(goal))
+            .collect();
+
+        while let Some(state) = to_visit.pop() {
+            if !states.insert(state) {
+                continue;
+            }
+            if let Some(predecessors) = self.predecessors.get(&state) {
+                to_visit.extend(predecessors);
+            }
+        }
+
+        return states;
+    }
+}
+
+// Search where every step costs 1. `neighbors` lists the states one step away from a
+// state and the search stops at the closest states `is_goal` accepts. Pass a goal that
+// is never reached to find the distance to every reachable state.
+pub fn bfs<S, N, I, G>(start: S, mut neighbors: N, mut is_goal: G) -> Search<S>
+where
+    S: Copy + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    G: FnMut(&S) -> bool,
+{
+    let mut search = Search::new(start);
+    let mut to_visit_queue = VecDeque::from([start]);
+
+    while let Some(state) = to_visit_queue.pop_front() {
+        let distance = search.distances[&state];
+        if search
+            .goal_distance()
+            .is_some_and(|goal_distance| distance > goal_distance)
+        {
+            break;
+        }
+
+        if is_goal(&state) {
+            search.goals.push(state);
+            continue;
+        }
+
+        for next in neighbors(&state) {
+            if search.relax(state, next, distance + 1) {
+                to_visit_queue.push_back(next);
+            }
+        }
+    }
+
+    return search;
+}
+
+// Search where `neighbors` lists the states one step away from a state along with
+// the cost of the step.
+pub fn dijkstra<S, N, I, G>(start: S, neighbors: N, is_goal: G) -> Search<S>
+where
+    S: Copy + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u32)>,
+    G: FnMut(&S) -> bool,
+{
+    return astar(start, neighbors, |_| 0, is_goal);
+}
+
+#[derive(PartialEq, Eq)]
+struct Visit<S> {
+    priority: u32,
+    distance: u32,
+    state: S,
+}
+
+// Create a custom ordering function so that the BinaryHeap priority queue will reorder itself to prioritize the least expensive moves.
+impl<S: Eq> Ord for Visit<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        return other.priority.cmp(&self.priority);
+    }
+}
+impl<S: Eq> PartialOrd for Visit<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+// Dijkstra's algorithm guided towards the goal by `heuristic`, an estimate of the
+// remaining cost that must never be more than the actual cost. The estimate can drop by
+// more than the cost of a step, in which case a state is visited again once a cheaper
+// way to it is found.
+pub fn astar<S, N, I, H, G>(
+    start: S,
+    mut neighbors: N,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> Search<S>
+where
+    S: Copy + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u32)>,
+    H: FnMut(&S) -> u32,
+    G: FnMut(&S) -> bool,
+{
+    let mut search = Search::new(start);
+    let mut to_visit_queue = BinaryHeap::from([Visit {
+        priority: heuristic(&start),
+        distance: 0,
+        state: start,
+    }]);
+
+    while let Some(Visit {
+        priority,
+        distance,
+        state,
+    }) = to_visit_queue.pop()
+    {
+        if search
+            .goal_distance()
+            .is_some_and(|goal_distance| priority > goal_distance)
+        {
+            break;
+        }
+
+        // A state is queued again each time a cheaper way to it is found, so skip the
+        // entries from the more expensive ways.
+        if search
+            .distance(&state)
+            .is_some_and(|known| distance > known)
+        {
+            continue;
+        }
+
+        if is_goal(&state) {
+            search.goals.push(state);
+            continue;
+        }
+
+        for (next, cost) in neighbors(&state) {
+            let next_distance = distance + cost;
+            if search.relax(state, next, next_distance) {
+                to_visit_queue.push(Visit {
+                    priority: next_distance + heuristic(&next),
+                    distance: next_distance,
+                    state: next,
+                });
+            }
+        }
+    }
+
+    return search;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 2 by 3 grid of states numbered row by row, with steps to the right and down.
+    //   0 1 2
+    //   3 4 5
+    fn right_and_down(&state: &u32) -> Vec<u32> {
+        let mut next = vec![];
+        if state % 3 < 2 {
+            next.push(state + 1);
+        }
+        if state < 3 {
+            next.push(state + 3);
+        }
+        return next;
+    }
+
+    #[test]
+    fn bfs_keeps_every_shortest_path() {
+        let search = bfs(0, right_and_down, |&state| state == 5);
+        assert_eq!(search.goals, [5]);
+        assert_eq!(search.goal_distance(), Some(3));
+        assert_eq!(search.path(5).unwrap().len(), 4);
+        assert_eq!(search.predecessors[&4].len(), 2);
+        assert_eq!(search.on_shortest_paths([5]).len(), 6);
+    }
+
+    #[test]
+    fn bfs_without_a_goal_reaches_everything() {
+        let search = bfs(1, right_and_down, |_| false);
+        assert_eq!(search.goals, []);
+        assert_eq!(search.distances.len(), 4);
+        assert_eq!(search.distance(&5), Some(2));
+        assert_eq!(search.distance(&0), None);
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_detours() {
+        // Going down is only cheap from the last column.
+        let weighted = |state: &u32| -> Vec<(u32, u32)> {
+            return right_and_down(state)
+                .into_iter()
+                .map(|next| {
+                    (
+                        next,
+                        if next == state + 3 && *state != 2 {
+                            5
+                        } else {
+                            1
+                        },
+                    )
+                })
+                .collect();
+        };
+
+        let search = dijkstra(0, weighted, |&state| state == 5);
+        assert_eq!(search.goal_distance(), Some(3));
+        assert_eq!(search.path(5).unwrap(), [0, 1, 2, 5]);
+        assert_eq!(search.on_shortest_paths([5]), HashSet::from([0, 1, 2, 5]));
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let size = 20;
+        let neighbors = |&(x, y): &(u32, u32)| -> Vec<((u32, u32), u32)> {
+            let mut next = vec![];
+            if x + 1 < size {
+                next.push(((x + 1, y), 1 + y % 3));
+            }
+            if y + 1 < size {
+                next.push(((x, y + 1), 1 + x % 2));
+            }
+            return next;
+        };
+        let goal = (size - 1, size - 1);
+        let is_goal = |state: &(u32, u32)| *state == goal;
+
+        let heuristic = |&(x, y): &(u32, u32)| (goal.0 - x) + (goal.1 - y);
+        let guided = astar((0, 0), neighbors, heuristic, is_goal);
+        let unguided = dijkstra((0, 0), neighbors, is_goal);
+
+        assert_eq!(guided.goal_distance(), unguided.goal_distance());
+        assert!(guided.distances.len() <= unguided.distances.len());
+    }
+
+    #[test]
+    fn astar_revisits_states_with_an_inconsistent_heuristic() {
+        // 0 -> 2 costs 3, but 0 -> 1 -> 2 only costs 2 and then 2 -> 3 costs 3.
+        // State 1 is estimated to be far from the goal, so state 2 is first reached the
+        // expensive way and has to be visited again after the cheap way is found.
+        let neighbors = |state: &u32| -> Vec<(u32, u32)> {
+            return match state {
+                0 => vec![(1, 1), (2, 3)],
+                1 => vec![(2, 1)],
+                2 => vec![(3, 3)],
+                _ => vec![],
+            };
+        };
+        let heuristic = |state: &u32| if *state == 1 { 4 } else { 0 };
+
+        let search = astar(0, neighbors, heuristic, |&state| state == 3);
+        assert_eq!(search.goal_distance(), Some(5));
+        assert_eq!(search.path(3).unwrap(), [0, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::util::point::Vector;
+use std::{collections::HashMap, fmt};
+
+// Cells spread over an unbounded plane, stored only where there's something. Unlike
+// `Grid`, positions can be negative and the grid grows as cells are added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Vector, T>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        return SparseGrid {
+            cells: HashMap::new(),
+        };
+    }
+
+    // Returns the value that was at `position` before, if there was one.
+    pub fn insert(&mut self, position: Vector, value: T) -> Option<T> {
+        return self.cells.insert(position, value);
+    }
+
+    pub fn remove(&mut self, position: Vector) -> Option<T> {
+        return self.cells.remove(&position);
+    }
+
+    pub fn get(&self, position: Vector) -> Option<&T> {
+        return self.cells.get(&position);
+    }
+
+    pub fn get_mut(&mut self, position: Vector) -> Option<&mut T> {
+        return self.cells.get_mut(&position);
+    }
+
+    // The value at `position`, inserting the one `default` makes if there isn't one.
+    pub fn entry_or_insert_with<F>(&mut self, position: Vector, default: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+    {
+        return self.cells.entry(position).or_insert_with(default);
+    }
+
+    pub fn contains(&self, position: Vector) -> bool {
+        return self.cells.contains_key(&position);
+    }
+
+    // The number of cells with something in them.
+    pub fn len(&self) -> usize {
+        return self.cells.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.cells.is_empty();
+    }
+
+    // Every filled cell, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vector, &T)> {
+        return self
+            .cells
+            .iter()
+            .map(|(&position, value)| (position, value));
+    }
+
+    // The top left and bottom right corners of the smallest rectangle holding every
+    // filled cell, both included. `None` when the grid is empty.
+    pub fn bounds(&self) -> Option<(Vector, Vector)> {
+        let mut positions = self.cells.keys();
+        let first = *positions.next()?;
+
+        return Some(positions.fold((first, first), |(min, max), position| {
+            return (
+                Vector::new(min.x.min(position.x), min.y.min(position.y)),
+                Vector::new(max.x.max(position.x), max.y.max(position.y)),
+            );
+        }));
+    }
+}
+
+impl<T: fmt::Display> SparseGrid<T> {
+    // Draw the cells inside the bounding box, one row per line, with `empty` where
+    // there's nothing.
+    pub fn render(&self, empty: char) -> String {
+        let Some((min, max)) = self.bounds() else {
+            return String::new();
+        };
+
+        let mut text = String::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                match self.get(Vector::new(x, y)) {
+                    Some(value) => text.push_str(&value.to_string()),
+                    None => text.push(empty),
+                }
+            }
+            text.push('\n');
+        }
+        return text;
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        return SparseGrid::new();
+    }
+}
+
+impl<T> FromIterator<(Vector, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Vector, T)>>(cells: I) -> Self {
+        return SparseGrid {
+            cells: cells.into_iter().collect(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grows_in_every_direction() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Vector::new(0, 0), 'a');
+        grid.insert(Vector::new(-2, 1), 'b');
+        grid.insert(Vector::new(1, -1), 'c');
+        assert_eq!(grid.insert(Vector::new(0, 0), 'd'), Some('a'));
+
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.get(Vector::new(-2, 1)), Some(&'b'));
+        assert_eq!(
+            grid.bounds(),
+            Some((Vector::new(-2, -1), Vector::new(1, 1)))
+        );
+        assert_eq!(grid.render('.'), "...c\n..d.\nb...\n");
+    }
+
+    #[test]
+    fn counts_with_entries() {
+        let mut counts: SparseGrid<u32> = SparseGrid::new();
+        for position in [Vector::new(3, 3), Vector::new(3, 3), Vector::new(-1, 0)] {
+            *counts.entry_or_insert_with(position, || 0) += 1;
+        }
+        assert_eq!(counts.get(Vector::new(3, 3)), Some(&2));
+        assert_eq!(counts.remove(Vector::new(-1, 0)), Some(1));
+        assert!(!counts.contains(Vector::new(-1, 0)));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::util::grid::Grid;
+use crate::util::point::Point;
+use std::ops::Index;
+
+// A borrowed rectangle of a grid's cells, indexed from its own top left corner.
+#[derive(Debug, Clone, Copy)]
+pub struct Window<'a, T> {
+    grid: &'a Grid<T>,
+    top_left: Point,
+    pub col_count: usize,
+    pub row_count: usize,
+}
+
+impl<'a, T> Window<'a, T> {
+    pub fn contains(&self, point: Point) -> bool {
+        return point.x < self.col_count && point.y < self.row_count;
+    }
+
+    pub fn get(&self, point: Point) -> Option<&'a T> {
+        if !self.contains(point) {
+            return None;
+        }
+        return self.grid.get(self.to_grid_point(point));
+    }
+
+    // The point in the underlying grid that `point` in the window refers to.
+    pub fn to_grid_point(&self, point: Point) -> Point {
+        return Point::new(self.top_left.x + point.x, self.top_left.y + point.y);
+    }
+
+    // The cells of every row of the window, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let Window {
+            grid,
+            top_left,
+            col_count,
+            row_count,
+        } = *self;
+        return (top_left.y..top_left.y + row_count)
+            .map(move |row| &grid.row(row)[top_left.x..top_left.x + col_count]);
+    }
+}
+
+impl<T: Clone> Window<'_, T> {
+    // Copy the cells of the window into a grid of their own.
+    pub fn to_grid(&self) -> Grid<T> {
+        return Grid {
+            col_count: self.col_count,
+            row_count: self.row_count,
+            contents: self.rows().flatten().cloned().collect(),
+        };
+    }
+}
+
+impl<T> Index<Point> for Window<'_, T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        assert!(self.contains(point), "{point:?} is outside the window");
+        return &self.grid[self.to_grid_point(point)];
+    }
+}
+
+impl<T> Grid<T> {
+    // The `col_count` by `row_count` rectangle with `top_left` as its top left corner,
+    // if it fits inside the grid.
+    pub fn window(
+        &self,
+        top_left: Point,
+        col_count: usize,
+        row_count: usize,
+    ) -> Option<Window<'_, T>> {
+        if top_left.x + col_count > self.col_count || top_left.y + row_count > self.row_count {
+            return None;
+        }
+
+        return Some(Window {
+            grid: self,
+            top_left,
+            col_count,
+            row_count,
+        });
+    }
+
+    // Every `col_count` by `row_count` rectangle that fits inside the grid, row by row.
+    pub fn windows(
+        &self,
+        col_count: usize,
+        row_count: usize,
+    ) -> impl Iterator<Item = Window<'_, T>> {
+        let rows = (self.row_count + 1).saturating_sub(row_count);
+        let cols = (self.col_count + 1).saturating_sub(col_count);
+        return (0..rows).flat_map(move |row| {
+            return (0..cols).map(move |col| Window {
+                grid: self,
+                top_left: Point::new(col, row),
+                col_count,
+                row_count,
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_index_from_their_corner() {
+        let grid: Grid<char> = "abcd\nefgh\nijkl\n".parse().unwrap();
+        let window = grid.window(Point::new(1, 1), 2, 2).unwrap();
+
+        assert_eq!(window[Point::new(0, 0)], 'f');
+        assert_eq!(window[Point::new(1, 1)], 'k');
+        assert_eq!(window.get(Point::new(2, 0)), None);
+        assert_eq!(window.to_grid().contents, ['f', 'g', 'j', 'k']);
+
+        assert!(grid.window(Point::new(3, 0), 2, 1).is_none());
+    }
+
+    #[test]
+    fn windows_cover_every_position() {
+        let grid: Grid<char> = "abcd\nefgh\nijkl\n".parse().unwrap();
+        let corners: String = grid
+            .windows(3, 2)
+            .map(|window| window[Point::new(0, 0)])
+            .collect();
+        assert_eq!(corners, "abef");
+        assert_eq!(grid.windows(5, 1).count(), 0);
+    }
+}
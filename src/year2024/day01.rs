@@ -1,35 +1,20 @@
+use crate::error::{self, Error};
+use crate::solution::Solution;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-// The output is wrapped in a Result to allow matching on errors.
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
 
-// Based on https://doc.rust-lang.org/rust-by-example/std_misc/file/read_lines.html
-fn read_file() -> (Vec<i32>, Vec<i32>) {
+fn read_file(input: &str) -> Result<(Vec<i32>, Vec<i32>), Error> {
     let mut list1: Vec<i32> = Vec::new();
     let mut list2: Vec<i32> = Vec::new();
-    if let Ok(lines) = read_lines("./input/year2024/day01.txt") {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines.flatten() {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            list1.push(parts[0].parse::<i32>().unwrap());
-            list2.push(parts[1].parse::<i32>().unwrap());
-        }
+    for line in error::numbered(input) {
+        let mut parts = line.text.split_whitespace();
+        list1.push(line.parse(line.expect(parts.next(), "a location ID")?)?);
+        list2.push(line.parse(line.expect(parts.next(), "a second location ID")?)?);
     }
-    return (list1, list2);
+    return Ok((list1, list2));
 }
 
 // Given two sorted lists, determine the difference between each entry.
-fn difference(list1: &Vec<i32>, list2: &Vec<i32>) -> i32 {
+fn difference(list1: &[i32], list2: &[i32]) -> i32 {
     let mut total_distance = 0;
 
     for n in 0..list1.len() {
@@ -39,7 +24,7 @@ fn difference(list1: &Vec<i32>, list2: &Vec<i32>) -> i32 {
     return total_distance;
 }
 
-fn create_location_hash(list: Vec<i32>) -> HashMap<i32, i32> {
+fn create_location_hash(list: &[i32]) -> HashMap<i32, i32> {
     let mut set = HashMap::new();
 
     let list_length = list.len();
@@ -59,6 +44,10 @@ fn create_location_hash(list: Vec<i32>) -> HashMap<i32, i32> {
         // Loop through the array until the end is met or the next value is not the same as the current.
         while n != list_length {
             n += 1;
+            // The last run of values ends with the list.
+            if n == list_length {
+                break;
+            }
             let next_value = list[n];
             // If t
             if next_value != current_value {
@@ -75,33 +64,93 @@ fn create_location_hash(list: Vec<i32>) -> HashMap<i32, i32> {
 
 // Calculate a total similarity score by adding up each number in the left list
 // after multiplying it by the number of times that number appears in the right list.
-fn similarity(list1: Vec<i32>, list2: Vec<i32>) -> i32 {
+fn similarity(list1: &[i32], list2: &[i32]) -> i32 {
     let mut simplicity_score = 0;
 
     let set2 = create_location_hash(list2);
 
-    for location_id in &list1 {
+    for location_id in list1 {
         // Find the count of the location_id in set2 to determine the similarity score of
         // this entry.
-        match set2.get(location_id) {
-            Some(count2) => simplicity_score += location_id * count2,
-            _ => {}
+        if let Some(count2) = set2.get(location_id) {
+            simplicity_score += location_id * count2
         }
     }
 
     return simplicity_score;
 }
 
-pub fn run() {
-    let (mut list1, mut list2) = read_file();
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = (Vec<i32>, Vec<i32>);
+    type Part1 = i32;
+    type Part2 = i32;
 
-    // Small data sets for testing
-    // let mut list1 = vec![3, 4, 2, 1, 3, 3];
-    // let mut list2 = vec![4, 3, 5, 3, 9, 3];
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let (mut list1, mut list2) = read_file(input)?;
 
-    list1.sort();
-    list2.sort();
+        list1.sort();
+        list2.sort();
 
-    println!("Total distance: {}", difference(&list1, &list2));
-    println!("Simplicity score: {}", similarity(list1, list2));
+        return Ok((list1, list2));
+    }
+
+    // Total distance
+    fn part1((list1, list2): &Self::Input) -> i32 {
+        return difference(list1, list2);
+    }
+
+    // Similarity score
+    fn part2((list1, list2): &Self::Input) -> i32 {
+        return similarity(list1, list2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+    #[test]
+    fn parse_sorts_both_lists() {
+        let (list1, list2) = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(list1, [1, 2, 3, 3, 3, 4]);
+        assert_eq!(list2, [3, 3, 3, 4, 5, 9]);
+    }
+
+    #[test]
+    fn part1_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&input), 11);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&input), 31);
+    }
+
+    #[test]
+    fn part2_counts_a_repeated_last_id() {
+        let input = Solver::parse("1   5\n5   5\n").unwrap();
+        assert_eq!(Solver::part2(&input), 10);
+    }
+
+    #[test]
+    fn parse_reports_the_offending_line() {
+        let error = Solver::parse("3   4\n4   x3\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 5: invalid value 'x3': invalid digit found in string"
+        );
+    }
 }
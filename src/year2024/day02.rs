@@ -1,96 +1,149 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-// The output is wrapped in a Result to allow matching on errors.
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+use crate::error::{self, Error};
+use crate::solution::Solution;
 
-// Based on https://doc.rust-lang.org/rust-by-example/std_misc/file/read_lines.html
-fn read_file(filename: &str) -> Vec<Vec<i32>> {
+fn read_file(input: &str) -> Result<Vec<Vec<i32>>, Error> {
     let mut reports: Vec<Vec<i32>> = Vec::new();
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines.flatten() {
-            let parts: Vec<i32> = line
-                .split_whitespace()
-                .map(|s| s.parse::<i32>().unwrap())
-                .collect();
-            reports.push(parts);
-        }
+    for line in error::numbered(input) {
+        let parts: Vec<i32> = line
+            .text
+            .split_whitespace()
+            .map(|s| line.parse::<i32>(s))
+            .collect::<Result<_, _>>()?;
+        reports.push(parts);
     }
-    return reports;
+    return Ok(reports);
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 enum ReportDirection {
     Up,
     Down,
 }
 
-pub fn run() {
-    let reports = read_file("./input/year2024/day02.txt");
-
-    let mut safe_report_count: i32 = 0;
-
-    for report in reports {
-        let level_length = report.len();
-
-        let mut report_direction: Option<ReportDirection> = None;
-
-        let mut failed_level_count = 0;
-
-        for i in 0..level_length {
-            // We only fail a report if there are many level failuers.
-            if failed_level_count > 1 {
-                break;
-            }
-
-            // If the end of the array has been found, pass the report.
-            if level_length == i + 1 {
-                safe_report_count += 1;
-                break;
-            }
-
-            let current_value = report[i];
-            let next_value = report[i + 1];
-
-            let difference: i32 = (current_value - next_value).abs();
-
-            // Check if the report is unsafe
-            if difference > 3 || difference < 1 {
-                failed_level_count += 1;
-                continue;
-            }
-
-            // All reports must either count up or count down. This checks
-            // that the report is counting the correct direction.
-            if report_direction == None {
-                if current_value > next_value {
-                    report_direction = Some(ReportDirection::Down);
-                } else {
-                    report_direction = Some(ReportDirection::Up);
-                }
-            } else {
-                // Check that the next value matches the required report direction
-                if current_value > next_value && report_direction == Some(ReportDirection::Up) {
-                    failed_level_count += 1;
-                    continue;
-                } else if current_value < next_value
-                    && report_direction == Some(ReportDirection::Down)
-                {
-                    failed_level_count += 1;
-                    continue;
-                }
-            }
+// A report is safe when its levels either all increase or all decrease, by 1 to 3 at a time.
+fn is_safe(report: &[i32]) -> bool {
+    let mut report_direction: Option<ReportDirection> = None;
+
+    for levels in report.windows(2) {
+        let current_value = levels[0];
+        let next_value = levels[1];
+
+        let difference: i32 = (current_value - next_value).abs();
+
+        // Check if the report is unsafe
+        if !(1..=3).contains(&difference) {
+            return false;
         }
+
+        let direction = if current_value > next_value {
+            ReportDirection::Down
+        } else {
+            ReportDirection::Up
+        };
+
+        // All reports must either count up or count down. This checks
+        // that the report is counting the correct direction.
+        if report_direction.is_some_and(|report_direction| report_direction != direction) {
+            return false;
+        }
+        report_direction = Some(direction);
     }
 
-    println!("Safe report count: {}", safe_report_count)
+    return true;
+}
+
+// A report that isn't safe can still pass if removing up to `tolerated_failures`
+// levels makes it safe.
+fn is_tolerably_safe(report: &[i32], tolerated_failures: u32) -> bool {
+    if is_safe(report) {
+        return true;
+    }
+    if tolerated_failures == 0 {
+        return false;
+    }
+
+    return (0..report.len()).any(|removed_index| {
+        let mut dampened_report = report.to_vec();
+        dampened_report.remove(removed_index);
+        return is_tolerably_safe(&dampened_report, tolerated_failures - 1);
+    });
+}
+
+// Count the reports that are safe when up to `tolerated_failures` levels are allowed to fail.
+fn count_safe_reports(reports: &[Vec<i32>], tolerated_failures: u32) -> i32 {
+    return reports
+        .iter()
+        .filter(|report| is_tolerably_safe(report, tolerated_failures))
+        .count() as i32;
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Vec<i32>>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return read_file(input);
+    }
+
+    fn part1(reports: &Self::Input) -> i32 {
+        return count_safe_reports(reports, 0);
+    }
+
+    // The Problem Dampener tolerates a single bad level.
+    fn part2(reports: &Self::Input) -> i32 {
+        return count_safe_reports(reports, 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+
+    #[test]
+    fn parse_example() {
+        let reports = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(reports.len(), 6);
+        assert_eq!(reports[0], [7, 6, 4, 2, 1]);
+    }
+
+    #[test]
+    fn part1_example() {
+        let reports = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&reports), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        let reports = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&reports), 4);
+    }
+
+    // The dampener used to count failing pairs of levels instead of removing a level,
+    // so a bad level in the middle counted as two failures, and a pair that failed once
+    // was skipped without checking the levels on either side of it.
+    #[test]
+    fn dampener_removes_a_level_instead_of_counting_failures() {
+        // Removing 5 leaves 1 2 3, but 1 -> 5 and 5 -> 2 both failed.
+        assert_eq!(count_safe_reports(&[vec![1, 5, 2, 3]], 1), 1);
+        // 2 -> 6 failed once, but no single level can be removed to close the gap.
+        assert_eq!(count_safe_reports(&[vec![1, 2, 6, 7]], 1), 0);
+    }
+
+    #[test]
+    fn dampener_can_remove_the_first_level() {
+        assert!(!is_safe(&[9, 1, 2, 3]));
+        assert!(is_tolerably_safe(&[9, 1, 2, 3], 1));
+    }
 }
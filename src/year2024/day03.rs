@@ -1,33 +1,17 @@
+use crate::error::Error;
+use crate::solution::Solution;
 use regex::Regex;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-// The output is wrapped in a Result to allow matching on errors.
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-fn read_file(filename: &str) -> String {
-    let mut program: String = "".to_owned();
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines.flatten() {
-            program.push_str(&line);
-        }
-    }
-    return program;
+
+fn read_file(input: &str) -> Result<String, Error> {
+    // The program is one string with the line breaks removed.
+    return Ok(input.lines().collect());
 }
 
-pub fn part1(program_instructions: &str) -> i32 {
+fn part1(program_instructions: &str) -> i32 {
     let mul_regex_pattern = Regex::new(r"mul\(([\d]{1,3}),([\d]{1,3})\)").unwrap();
 
     let matches: Vec<(i32, i32)> = mul_regex_pattern
-        .captures_iter(&program_instructions)
+        .captures_iter(program_instructions)
         .map(|mul| {
             let (_, [x, y]) = mul.extract();
             return (x.parse::<i32>().unwrap(), y.parse::<i32>().unwrap());
@@ -39,7 +23,6 @@ pub fn part1(program_instructions: &str) -> i32 {
         instruction_result += match_pair.0 * match_pair.1;
     }
 
-    println!("{:?}", instruction_result);
     return instruction_result;
 }
 
@@ -49,7 +32,7 @@ pub fn part1(program_instructions: &str) -> i32 {
 // multiple lookarounds while also trying to skip invalid characters was proving very complex.
 // 3. While Regex can be short, it's not very maintainable. This loop should be more readable then
 // a very complex Regex.
-pub fn part2(program_instructions: &str) -> i32 {
+fn part2(program_instructions: &str) -> i32 {
     let mul_regex_pattern = Regex::new(r"(mul\(([\d]{1,3}),([\d]{1,3})\))").unwrap();
 
     let mut unprocessed_instructions: &str = program_instructions;
@@ -57,7 +40,7 @@ pub fn part2(program_instructions: &str) -> i32 {
     let mut instructions_enabled: bool = true;
     let mut instruction_result: i32 = 0;
 
-    while unprocessed_instructions.len() > 0 {
+    while !unprocessed_instructions.is_empty() {
         let do_instruction = unprocessed_instructions.find("do()");
 
         if !instructions_enabled {
@@ -80,9 +63,8 @@ pub fn part2(program_instructions: &str) -> i32 {
                 let dont_instruction = unprocessed_instructions.find("don't()");
 
                 // If the next multiply instruction is farther then the next dont, jump to the dont and loop again.
-                if Some(mul_start) > dont_instruction && dont_instruction != None {
-                    unprocessed_instructions =
-                        &unprocessed_instructions[(dont_instruction.unwrap() + 1)..];
+                if let Some(dont_start) = dont_instruction.filter(|&i| mul_start > i) {
+                    unprocessed_instructions = &unprocessed_instructions[(dont_start + 1)..];
                     instructions_enabled = false;
                     continue;
                 // Otherwise process the next multiply instruction.
@@ -102,12 +84,53 @@ pub fn part2(program_instructions: &str) -> i32 {
         }
     }
 
-    println!("{:?}", instruction_result);
     return instruction_result;
 }
 
-pub fn run() -> (i32, i32) {
-    let program_instructions = read_file("input/year2024/day03.txt");
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = String;
+    type Part1 = i32;
+    type Part2 = i32;
 
-    return (part1(&program_instructions), part2(&program_instructions));
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return read_file(input);
+    }
+
+    fn part1(program_instructions: &Self::Input) -> i32 {
+        return part1(program_instructions);
+    }
+
+    fn part2(program_instructions: &Self::Input) -> i32 {
+        return part2(program_instructions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART1_EXAMPLE: &str =
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))\n";
+    const PART2_EXAMPLE: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))\n";
+
+    #[test]
+    fn parse_joins_lines() {
+        let program = Solver::parse("mul(1,\n2)\n").unwrap();
+        assert_eq!(program, "mul(1,2)");
+    }
+
+    #[test]
+    fn part1_example() {
+        let program = Solver::parse(PART1_EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&program), 161);
+    }
+
+    #[test]
+    fn part2_example() {
+        let program = Solver::parse(PART2_EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&program), 48);
+    }
 }
@@ -1,103 +1,47 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-// The output is wrapped in a Result to allow matching on errors.
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+use crate::error::{self, Error};
+use crate::solution::Solution;
+use crate::util::grid::Grid;
+use crate::util::point::Point;
+
+fn read_file(input: &str) -> Result<Grid<char>, Error> {
+    return Grid::from_chars(error::numbered(input));
 }
 
-fn read_file(filename: &str) -> Vec<Vec<char>> {
-    return read_lines(filename)
-        .unwrap()
-        .flatten()
-        .map(|line| line.chars().collect())
-        .collect();
+const XMAS: [char; 4] = ['X', 'M', 'A', 'S'];
+
+// Count the instances of "XMAS" written left to right or diagonally down and to the right.
+fn count_forwards(word_search: &Grid<char>) -> usize {
+    let across: usize = word_search
+        .rows()
+        .map(|row| row.windows(XMAS.len()).filter(|&word| word == XMAS).count())
+        .sum();
+
+    let diagonal = word_search
+        .windows(XMAS.len(), XMAS.len())
+        .filter(|window| {
+            return XMAS
+                .iter()
+                .enumerate()
+                .all(|(index, &letter)| window[Point::new(index, index)] == letter);
+        })
+        .count();
+
+    return across + diagonal;
 }
 
 // Given a word search, find the total count of instances of "XMAS".
 // All orientations count, including horizontal, vertical, diagonal, written backwards,
 // and overlapping.
-fn part1(word_search: Vec<Vec<char>>) -> i32 {
-    let mut count = 0;
-
-    let mut row_index = 0;
-    while word_search.len() > row_index {
-        let line = &word_search[row_index];
-
-        let line_length = line.len();
-
-        let mut i = 0;
-
-        while line_length > i {
-            // Checking for "X" finds all forward cases.
-            // Checking for "S" finds all reverse cases.
-            // All other letters will get reviewed with the "X" or "S" check.
-            if line[i] != 'X' && line[i] != 'S' {
-                i += 1;
-                continue;
-            }
-
-            let space_available_right = i + 3 < line_length;
-            let space_available_up = row_index >= 3;
-            let space_available_down = row_index + 3 < line_length;
-
-            // Forward horizontal case
-            if space_available_right {
-                let found_string = line[i].to_string()
-                    + &line[i + 1].to_string()
-                    + &line[i + 2].to_string()
-                    + &line[i + 3].to_string();
-                if found_string == "XMAS" || found_string == "SAMX" {
-                    count += 1;
-                }
-            }
-
-            // Down vertical case
-            if space_available_down {
-                let found_string: String = line[i].to_string()
-                    + &word_search[row_index + 1][i].to_string()
-                    + &word_search[row_index + 2][i].to_string()
-                    + &word_search[row_index + 3][i].to_string();
-                if found_string == "XMAS" || found_string == "SAMX" {
-                    count += 1;
-                }
-            }
-
-            // Diagonal up case
-            if space_available_right && space_available_up {
-                let found_string: String = line[i].to_string()
-                    + &word_search[row_index - 1][i + 1].to_string()
-                    + &word_search[row_index - 2][i + 2].to_string()
-                    + &word_search[row_index - 3][i + 3].to_string();
-                if found_string == "XMAS" || found_string == "SAMX" {
-                    count += 1;
-                }
-            }
-
-            // Diagonal down case
-            if space_available_right && space_available_down {
-                let found_string: String = line[i].to_string()
-                    + &word_search[row_index + 1][i + 1].to_string()
-                    + &word_search[row_index + 2][i + 2].to_string()
-                    + &word_search[row_index + 3][i + 3].to_string();
-                if found_string == "XMAS" || found_string == "SAMX" {
-                    count += 1;
-                }
-            }
-
-            i += 1;
-        }
-
-        row_index += 1;
-    }
-
-    return count;
+fn part1(word_search: &Grid<char>) -> usize {
+    // Each quarter turn brings a different pair of the eight orientations around to
+    // left to right and diagonally down and to the right.
+    let turned = [
+        word_search.rotate_90(),
+        word_search.rotate_180(),
+        word_search.rotate_270(),
+    ];
+
+    return count_forwards(word_search) + turned.iter().map(count_forwards).sum::<usize>();
 }
 
 // Given a word search, find the total count of instances where the string "MAS" forms a cross
@@ -105,66 +49,97 @@ fn part1(word_search: Vec<Vec<char>>) -> i32 {
 // M.S
 // .A.
 // M.S
-fn part2(word_search: Vec<Vec<char>>) -> i32 {
-    let mut count = 0;
-
-    let mut row_index = 0;
-    while word_search.len() > row_index {
-        let line = &word_search[row_index];
-
-        let line_length = line.len();
-
-        let mut i = 0;
-
-        while line_length > i {
-            // Check for "A" since it will be the center of the cross.
-            if line[i] != 'A' {
-                i += 1;
-                continue;
-            }
-
-            let space_available_left = i >= 1;
-            let space_available_right = i + 1 < line_length;
-            let space_available_up = row_index >= 1;
-            let space_available_down = row_index + 1 < line_length;
-
-            // If the value overlaps to unavailable space, continue to the next index
-            // because there isn't enough space for a valid index.
-            if !space_available_left
-                || !space_available_right
-                || !space_available_up
-                || !space_available_down
-            {
-                i += 1;
-                continue;
-            }
-
-            let diagnol1: String = word_search[row_index - 1][i - 1].to_string()
-                + &word_search[row_index + 1][i + 1].to_string();
-            let diagnol2: String = word_search[row_index + 1][i - 1].to_string()
-                + &word_search[row_index - 1][i + 1].to_string();
-
-            // Check that each diagnol corner matches the expected combinations.
-            let valid_diagnol1 = diagnol1 == "MS" || diagnol1 == "SM";
-            let valid_diagnol2 = diagnol2 == "MS" || diagnol2 == "SM";
-
-            if valid_diagnol1 && valid_diagnol2 {
-                count += 1;
-                i += 1;
-                continue;
-            }
-
-            i += 1;
-        }
-
-        row_index += 1;
+fn part2(word_search: &Grid<char>) -> usize {
+    // '.' matches any letter. The four quarter turns of the cross cover every way the two
+    // "MAS" can be written.
+    let cross: Grid<char> = "M.S\n.A.\nM.S\n".parse().expect("The cross is a grid");
+    let crosses = [
+        cross.rotate_90(),
+        cross.rotate_180(),
+        cross.rotate_270(),
+        cross,
+    ];
+
+    return word_search
+        .windows(3, 3)
+        .filter(|window| {
+            return crosses.iter().any(|cross| {
+                return cross
+                    .iter()
+                    .all(|(point, &letter)| letter == '.' || letter == window[point]);
+            });
+        })
+        .count();
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Grid<char>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return read_file(input);
     }
 
-    return count;
+    fn part1(word_search: &Self::Input) -> usize {
+        return part1(word_search);
+    }
+
+    fn part2(word_search: &Self::Input) -> usize {
+        return part2(word_search);
+    }
 }
 
-pub fn run() -> (i32, i32) {
-    let word_search = read_file("./input/year2024/day04.txt");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+";
+
+    #[test]
+    fn parse_example() {
+        let word_search = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!((word_search.row_count, word_search.col_count), (10, 10));
+    }
+
+    #[test]
+    fn part1_example() {
+        let word_search = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&word_search), 18);
+    }
+
+    #[test]
+    fn part2_example() {
+        let word_search = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&word_search), 9);
+    }
 
-    return (part1(word_search.clone()), part2(word_search));
+    // Grids that aren't square used to be checked against the wrong edge.
+    #[test]
+    fn part1_rectangular_grids() {
+        let wide = Solver::parse("XMAS\n....\n").unwrap();
+        assert_eq!(Solver::part1(&wide), 1);
+
+        let tall = Solver::parse("X\nM\nA\nS\n").unwrap();
+        assert_eq!(Solver::part1(&tall), 1);
+    }
+
+    #[test]
+    fn part2_rectangular_grids() {
+        let wide = Solver::parse("M.S..\n.A...\nM.S..\n.A...\n").unwrap();
+        assert_eq!(Solver::part2(&wide), 1);
+    }
 }
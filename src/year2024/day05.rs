@@ -1,51 +1,36 @@
+use crate::error::Error;
+use crate::solution::Solution;
+use crate::util::file;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-// The output is wrapped in a Result to allow matching on errors.
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-fn read_file(filename: &str) -> (HashMap<i32, Vec<i32>>, Vec<Vec<i32>>) {
-    let mut ordering_rules: HashMap<i32, Vec<i32>> = HashMap::new();
-    let mut page_updates = vec![];
 
-    for line in read_lines(filename).unwrap().flatten() {
-        // If the line has "|", it is an ordering rule.
-        if line.contains("|") {
-            let mut input_iter = line.split("|");
+type Input = (HashMap<i32, Vec<i32>>, Vec<Vec<i32>>);
 
-            let key = input_iter.next().unwrap().parse::<i32>().unwrap();
-            let value = input_iter.next().unwrap().parse::<i32>().unwrap();
+fn read_file(input: &str) -> Result<Input, Error> {
+    let (rules, updates) = file::two_sections(input, "the ordering rules", "the page updates")?;
 
-            // Append to the vector already on the HashMap if it exists.
-            if let Some(vec) = ordering_rules.get_mut(&key) {
-                vec.push(value);
-            } else {
-                ordering_rules.insert(key, vec![value]);
-            }
-        // If the line has ",", it is a page update
-        } else if line.contains(",") {
-            let mut updates = vec![];
-
-            for input in line.split(",") {
-                updates.push(input.parse::<i32>().unwrap());
-            }
-            page_updates.push(updates);
+    let mut ordering_rules: HashMap<i32, Vec<i32>> = HashMap::new();
+    for line in &rules {
+        let [key, value] = file::list::<i32>(line, "|")?[..] else {
+            return Err(line.error("expected an ordering rule like '47|53'"));
+        };
+
+        // Append to the vector already on the HashMap if it exists.
+        if let Some(vec) = ordering_rules.get_mut(&key) {
+            vec.push(value);
+        } else {
+            ordering_rules.insert(key, vec![value]);
         }
     }
 
-    return (ordering_rules, page_updates);
+    let page_updates = updates
+        .iter()
+        .map(|line| file::list::<i32>(line, ","))
+        .collect::<Result<_, _>>()?;
+
+    return Ok((ordering_rules, page_updates));
 }
 
-fn part1((ordering_rules, page_updates): (HashMap<i32, Vec<i32>>, Vec<Vec<i32>>)) -> i32 {
+fn part1((ordering_rules, page_updates): &Input) -> i32 {
     let mut middle_page_number_total: i32 = 0;
 
     for update in page_updates {
@@ -57,20 +42,16 @@ fn part1((ordering_rules, page_updates): (HashMap<i32, Vec<i32>>, Vec<Vec<i32>>)
         while i < update_length {
             let value = update[i];
 
-            match ordering_rules.get(&value) {
-                Some(after_values) => {
-                    // Check all the values before this index to see if they should actually be after the index.
-                    value_incorrectly_before = update[..i]
-                        .iter()
-                        .filter(|&value| after_values.contains(value))
-                        .next()
-                        != None;
-
-                    if value_incorrectly_before {
-                        break;
-                    }
+            if let Some(after_values) = ordering_rules.get(&value) {
+                // Check all the values before this index to see if they should actually be after the index.
+                value_incorrectly_before = update[..i]
+                    .iter()
+                    .find(|&value| after_values.contains(value))
+                    .is_some();
+
+                if value_incorrectly_before {
+                    break;
                 }
-                _ => {}
             }
 
             i += 1;
@@ -78,7 +59,7 @@ fn part1((ordering_rules, page_updates): (HashMap<i32, Vec<i32>>, Vec<Vec<i32>>)
 
         // Only add the middle index value if all the values are
         // in the correct locations.
-        if value_incorrectly_before == false {
+        if !value_incorrectly_before {
             let middle_index = update_length / 2;
 
             middle_page_number_total += update[middle_index];
@@ -90,46 +71,42 @@ fn part1((ordering_rules, page_updates): (HashMap<i32, Vec<i32>>, Vec<Vec<i32>>)
 
 // Move pages into valid locations.
 // Return the sum of the middle index of all arrays that received an update.
-fn part2((ordering_rules, page_updates): (HashMap<i32, Vec<i32>>, Vec<Vec<i32>>)) -> i32 {
+fn part2((ordering_rules, page_updates): &Input) -> i32 {
     let mut middle_page_number_total: i32 = 0;
 
-    for mut update in page_updates {
+    for update in page_updates {
+        let mut update = update.clone();
         let mut has_changed = false;
 
         let mut i = update.len() - 1;
 
         while i > 0 {
-            match ordering_rules.get(&update[i]) {
-                Some(after_values) => {
-                    let mut compare_i = i - 1;
-                    // Loop until we are out of indexes to compare against.
-                    loop {
-                        // If the compared index value is not supposed to go after the current index,
-                        // no switch is necessary and we can continue.
-                        if !after_values.contains(&update[compare_i]) {
-                            if compare_i == 0 {
-                                break;
-                            }
-
-                            compare_i -= 1;
-
-                            continue;
+            if let Some(after_values) = ordering_rules.get(&update[i]) {
+                let mut compare_i = i - 1;
+                // Loop until we are out of indexes to compare against.
+                loop {
+                    // If the compared index value is not supposed to go after the current index,
+                    // no switch is necessary and we can continue.
+                    if !after_values.contains(&update[compare_i]) {
+                        if compare_i == 0 {
+                            break;
                         }
 
-                        // Otherwise we must switch the index values.
-                        has_changed = true;
-
-                        let temp = update[i];
-                        update[i] = update[compare_i];
-                        update[compare_i] = temp;
+                        compare_i -= 1;
 
-                        // We need to re-run the search because the switch
-                        // may move numbers into an order that needs to be re-compared.
-                        i += 1;
-                        break;
+                        continue;
                     }
+
+                    // Otherwise we must switch the index values.
+                    has_changed = true;
+
+                    update.swap(i, compare_i);
+
+                    // We need to re-run the search because the switch
+                    // may move numbers into an order that needs to be re-compared.
+                    i += 1;
+                    break;
                 }
-                _ => {}
             }
 
             i -= 1;
@@ -149,14 +126,78 @@ fn part2((ordering_rules, page_updates): (HashMap<i32, Vec<i32>>, Vec<Vec<i32>>)
     return middle_page_number_total;
 }
 
-pub fn run() -> (i32, i32) {
-    let input = read_file("./input/year2024/day05.txt");
+pub struct Solver;
 
-    let result1 = part1(input.clone());
-    let result2 = part2(input);
+impl Solution for Solver {
+    type Input = Input;
+    type Part1 = i32;
+    type Part2 = i32;
 
-    println!("Part1: {:?}", result1);
-    println!("Part2: {:?}", result2);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return read_file(input);
+    }
 
-    return (result1, result2);
+    fn part1(input: &Self::Input) -> i32 {
+        return part1(input);
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        return part2(input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+";
+
+    #[test]
+    fn parse_example() {
+        let (ordering_rules, page_updates) = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(ordering_rules[&97], [13, 61, 47, 29, 53, 75]);
+        assert_eq!(page_updates.len(), 6);
+        assert_eq!(page_updates[2], [75, 29, 13]);
+    }
+
+    #[test]
+    fn part1_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&input), 143);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&input), 123);
+    }
 }
@@ -1,83 +1,41 @@
-use crate::util::file::read;
-use grid::*;
+use crate::error::{self, Error};
+use crate::solution::Solution;
+use crate::util::{direction::Direction, grid::Grid, point::Point};
 use std::collections::HashSet;
 
-fn process_file(filename: &str) -> Grid<char> {
-    let mut grid = grid![];
-
-    if let Ok(lines) = read(filename) {
-        lines
-            .flatten()
-            .for_each(|line| grid.push_row(line.chars().collect()));
+fn process_file(input: &str) -> Result<Grid<char>, Error> {
+    let grid = Grid::from_chars(error::numbered(input))?;
+    if grid.find_index(|&cell| cell == '^').is_none() {
+        return Err(Error::Input("the map has no guard '^'".to_string()));
     }
 
-    return grid;
-}
-
-#[derive(Debug, Hash, Eq, PartialEq, Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+    return Ok(grid);
 }
 
 #[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
 struct GridCell {
-    index: (usize, usize),
+    index: Point,
     direction: Direction,
 }
 
 fn find_initial_cords(grid: &Grid<char>) -> GridCell {
     // Find the initial x,y coordinates of the guard
-    let mut initial_coordinates: Option<(usize, usize)> = None;
-    for cell in grid.indexed_iter() {
-        if cell.1 == &'^' {
-            initial_coordinates = Some(cell.0);
-            break;
-        }
-    }
+    let (row, col) = grid
+        .find_index(|cell| cell == &'^')
+        .expect("The parser checks for the guard");
 
     return GridCell {
-        index: initial_coordinates.unwrap(),
+        index: Point::new(col, row),
         // The initial direction is always up.
         direction: Direction::Up,
     };
 }
 
-fn get_next_state(
-    grid: &Grid<char>,
-    &GridCell {
-        index: (row_i, col_i),
-        direction,
-    }: &GridCell,
-) -> Option<(usize, usize)> {
-    let (row_count, col_count) = grid.size();
-
-    let exit_grid = match direction {
-        Direction::Up => row_i == 0,
-        Direction::Down => row_i + 1 == row_count,
-        Direction::Right => col_i + 1 == col_count,
-        Direction::Left => col_i == 0,
-    };
-
-    if exit_grid {
-        return None;
-    }
-
-    return match direction {
-        Direction::Up => Some((row_i - 1, col_i)),
-        Direction::Down => Some((row_i + 1, col_i)),
-        Direction::Right => Some((row_i, col_i + 1)),
-        Direction::Left => Some((row_i, col_i - 1)),
-    };
-}
-
 // Given a grid, walk until end_cords is reached.
 // A callback function is called on every iteration of the loop.
 fn process_grid(grid: &Grid<char>, initial_cords: GridCell) -> Option<HashSet<GridCell>> {
     let GridCell {
-        index: (mut row_i, mut col_i),
+        mut index,
         mut direction,
     } = initial_cords;
 
@@ -86,30 +44,13 @@ fn process_grid(grid: &Grid<char>, initial_cords: GridCell) -> Option<HashSet<Gr
     path_steps.insert(initial_cords);
 
     loop {
-        let next_cords = get_next_state(
-            grid,
-            &GridCell {
-                index: (row_i, col_i),
-                direction,
-            },
-        );
-
-        if next_cords == None {
+        let Some(next_index) = grid.step(index, direction) else {
             break Some(path_steps);
-        }
-
-        let (next_row_i, next_col_i) = next_cords.unwrap();
-
-        let grid_next = grid.get(next_row_i, next_col_i).unwrap();
+        };
 
         // If there is an obstacle, rotate the direction and loop again
-        if grid_next == &'#' {
-            direction = match direction {
-                Direction::Up => Direction::Right,
-                Direction::Right => Direction::Down,
-                Direction::Down => Direction::Left,
-                Direction::Left => Direction::Up,
-            };
+        if grid[next_index] == '#' {
+            direction = direction.clockwise();
             continue;
         };
 
@@ -117,33 +58,38 @@ fn process_grid(grid: &Grid<char>, initial_cords: GridCell) -> Option<HashSet<Gr
         // If we've already been to an index with the same direction, it means
         // the guard is stuck in an infinite loop.
         let is_new_state = path_steps.insert(GridCell {
-            index: (next_row_i, next_col_i),
-            direction: direction,
+            index: next_index,
+            direction,
         });
 
-        if is_new_state == false {
+        if !is_new_state {
             break None;
         }
 
-        (row_i, col_i) = (next_row_i, next_col_i);
+        index = next_index;
     }
 }
 
-pub fn run() -> (usize, usize) {
-    let grid = process_file("input/year2024/day06.txt");
-    let mut part2_grid = grid.clone();
+// Get all the cells the guard walks through, without direction included.
+fn visited_cells(grid: &Grid<char>) -> HashSet<Point> {
+    let unique_steps = process_grid(grid, find_initial_cords(grid))
+        .expect("The guard must leave the grid without obstacles added");
 
-    let initial_cords = find_initial_cords(&grid);
+    return HashSet::from_iter(unique_steps.iter().map(|v| v.index));
+}
 
-    let unique_steps = process_grid(&grid, initial_cords).unwrap();
+fn part1(grid: &Grid<char>) -> usize {
+    return visited_cells(grid).len();
+}
 
-    // Get all the steps without direction included. This is necesary for part 1.
-    let consolidated_steps: HashSet<(usize, usize)> =
-        HashSet::from_iter(unique_steps.iter().map(|v| v.index));
+// Count the cells where a single new obstacle makes the guard loop forever.
+// Only cells on the guard's original path can change the route.
+fn part2(grid: &Grid<char>) -> usize {
+    let mut part2_grid = grid.clone();
 
-    let part1_count = consolidated_steps.len();
+    let initial_cords = find_initial_cords(grid);
 
-    let part2_count = consolidated_steps
+    return visited_cells(grid)
         .iter()
         .filter_map(|&cell| {
             // Add a temporary obstacle
@@ -160,11 +106,73 @@ pub fn run() -> (usize, usize) {
                 None => Some(true),
             }
         })
-        .collect::<Vec<bool>>()
-        .len();
+        .count();
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Grid<char>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    fn part1(grid: &Self::Input) -> usize {
+        return part1(grid);
+    }
 
-    println!("{:?}", part1_count);
-    println!("{:?}", part2_count);
+    fn part2(grid: &Self::Input) -> usize {
+        return part2(grid);
+    }
+}
 
-    return (part1_count, part2_count);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    #[test]
+    fn parse_example() {
+        let grid = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!((grid.row_count, grid.col_count), (10, 10));
+        assert_eq!(grid[Point::new(4, 6)], '^');
+    }
+
+    #[test]
+    fn part1_example() {
+        let grid = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&grid), 41);
+    }
+
+    #[test]
+    fn part2_example() {
+        let grid = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&grid), 6);
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        assert!(Solver::parse("...\n..\n").is_err());
+    }
+
+    #[test]
+    fn parse_requires_a_guard() {
+        let error = Solver::parse("..#\n...\n").unwrap_err();
+        assert_eq!(error.to_string(), "the map has no guard '^'");
+    }
 }
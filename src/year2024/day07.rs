@@ -1,29 +1,25 @@
-use crate::util::file::read;
+use crate::error::{self, Error};
+use crate::solution::Solution;
 
-fn process_file(filename: &str) -> Vec<(u64, Vec<u64>)> {
+fn process_file(input: &str) -> Result<Vec<(u64, Vec<u64>)>, Error> {
     let mut vec = vec![];
 
-    read(filename)
-        .unwrap()
-        .into_iter()
-        .map(|line| line.unwrap())
-        .for_each(|line| {
-            let mut iter = line.split(": ");
-            // Get the total for the row
-            let total = iter.next().unwrap().parse::<u64>().unwrap();
-
-            // Find all the values associated with the total.
-            let values = iter
-                .next()
-                .unwrap()
-                .split(' ')
-                .map(|num| num.parse::<u64>().unwrap())
-                .collect::<Vec<u64>>();
-
-            vec.push((total, values));
-        });
-
-    return vec;
+    for line in error::numbered(input) {
+        let mut iter = line.text.split(": ");
+        // Get the total for the row
+        let total = line.parse::<u64>(line.expect(iter.next(), "a test value")?)?;
+
+        // Find all the values associated with the total.
+        let values = line
+            .expect(iter.next(), "': ' followed by numbers")?
+            .split(' ')
+            .map(|num| line.parse::<u64>(num))
+            .collect::<Result<Vec<u64>, Error>>()?;
+
+        vec.push((total, values));
+    }
+
+    return Ok(vec);
 }
 
 fn evaluate(
@@ -32,7 +28,7 @@ fn evaluate(
     values: &[u64],
     check_concatenation: &bool,
 ) -> Option<u64> {
-    if values.len() == 0 {
+    if values.is_empty() {
         if total == &current_value {
             return Some(current_value);
         } else {
@@ -51,9 +47,9 @@ fn evaluate(
     // Evaluate the * case
     let case = evaluate(
         total,
-        &current_value * &value,
+        current_value * value,
         &values[1..],
-        &check_concatenation,
+        check_concatenation,
     );
 
     if case.is_some() {
@@ -69,7 +65,7 @@ fn evaluate(
                 .parse::<u64>()
                 .unwrap(),
             &values[1..],
-            &check_concatenation,
+            check_concatenation,
         );
 
         if case.is_some() {
@@ -80,29 +76,74 @@ fn evaluate(
     // If it fails, evalaute the + case
     let case = evaluate(
         total,
-        &current_value + &value,
+        current_value + value,
         &values[1..],
-        &check_concatenation,
+        check_concatenation,
     );
 
     return case;
 }
 
-fn get_calibration_result(vec: &Vec<(u64, Vec<u64>)>, check_concatenation: bool) -> u64 {
+fn get_calibration_result(vec: &[(u64, Vec<u64>)], check_concatenation: bool) -> u64 {
     vec.iter()
-        .map(|(total, values)| evaluate(total, values[0], &values[1..], &check_concatenation))
-        .flatten()
+        .filter_map(|(total, values)| {
+            evaluate(total, values[0], &values[1..], &check_concatenation)
+        })
         .sum()
 }
 
-pub fn run() -> (usize, usize) {
-    let vec = process_file("input/year2024/day07.txt");
+pub struct Solver;
 
-    let part1_count = get_calibration_result(&vec, false);
-    let part2_count = get_calibration_result(&vec, true);
+impl Solution for Solver {
+    type Input = Vec<(u64, Vec<u64>)>;
+    type Part1 = u64;
+    type Part2 = u64;
 
-    println!("{:?}", part1_count);
-    println!("{:?}", part2_count);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    fn part1(equations: &Self::Input) -> u64 {
+        return get_calibration_result(equations, false);
+    }
 
-    return (0, 0);
+    fn part2(equations: &Self::Input) -> u64 {
+        return get_calibration_result(equations, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+
+    #[test]
+    fn parse_example() {
+        let equations = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(equations.len(), 9);
+        assert_eq!(equations[1], (3267, vec![81, 40, 27]));
+    }
+
+    #[test]
+    fn part1_example() {
+        let equations = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&equations), 3749);
+    }
+
+    #[test]
+    fn part2_example() {
+        let equations = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&equations), 11387);
+    }
 }
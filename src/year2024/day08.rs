@@ -1,165 +1,168 @@
-use crate::util::file::read;
-use std::collections::{HashMap, HashSet};
-
-fn process_file(filename: &str) -> Vec<Vec<char>> {
-    return read(filename)
-        .unwrap()
-        .into_iter()
-        .map(|line| line.unwrap().chars().collect())
-        .collect();
-}
-
-// Calculate the position of an antinode given details about the antennas.
-fn calculate_antinode_pos(pos: usize, difference: usize, size: usize, add: bool) -> Option<usize> {
-    if add {
-        let new_pos = pos + difference;
-        if new_pos >= size {
-            return None;
-        }
-        return Some(new_pos);
-    } else {
-        return pos.checked_sub(difference);
-    };
-}
-
-fn calculate_antinode(
-    position: (usize, usize),
-    size: (usize, usize),
-    difference: (usize, usize),
-    add: (bool, bool),
-    find_harmonics: bool,
-) -> Vec<(usize, usize)> {
-    let antinode = (
-        calculate_antinode_pos(position.0, difference.0, size.0, add.0),
-        calculate_antinode_pos(position.1, difference.1, size.1, add.1),
-    );
-    if antinode.0.is_none() || antinode.1.is_none() {
-        return vec![];
-    }
-    let antinode_parsed = (antinode.0.unwrap(), antinode.1.unwrap());
-
-    // If we need to find harmonics, search recursively for valid harmonics.
-    let mut antinode_locations = if find_harmonics {
-        calculate_antinode(antinode_parsed, size, difference, add, find_harmonics)
-    } else {
-        vec![]
-    };
-    antinode_locations.push(antinode_parsed);
-
-    return antinode_locations;
+use crate::error::{self, Error};
+use crate::solution::Solution;
+use crate::util::grid::Grid;
+use crate::util::point::{Point, Vector};
+use crate::util::sparse::SparseGrid;
+use std::collections::HashMap;
+
+fn process_file(input: &str) -> Result<Grid<char>, Error> {
+    return Grid::from_chars(error::numbered(input));
 }
 
+// Find the antinodes of a pair of antennas with the same frequency. They're in line
+// with the antennas, a step of the distance between them beyond each antenna. With
+// harmonics, every whole number of steps counts, including zero steps at the antennas
+// themselves.
 fn find_all_antinodes(
-    position1: (usize, usize),
-    position2: (usize, usize),
-    size: (usize, usize),
+    grid: &Grid<char>,
+    antenna1: Vector,
+    antenna2: Vector,
     find_harmonics: bool,
-) -> Vec<(usize, usize)> {
-    // Determine where the element is relative to the other antennas.
-    let antenna1_is_right = position1.0 > position2.0;
-    let antenna1_is_below = position1.1 > position2.1;
-
-    // Get the difference between the two vectors so we can calculate the positions of the antinodes.
-    let row_difference = if antenna1_is_right {
-        position1.0 - position2.0
-    } else {
-        position2.0 - position1.0
-    };
-    let col_difference = if antenna1_is_below {
-        position1.1 - position2.1
-    } else {
-        position2.1 - position1.1
-    };
-
-    let mut antinode1 = calculate_antinode(
-        position1,
-        size,
-        (row_difference, col_difference),
-        (antenna1_is_right, antenna1_is_below),
-        find_harmonics,
-    );
-    let mut antinode2 = calculate_antinode(
-        position2,
-        size,
-        (row_difference, col_difference),
-        // Invert the direction to add antinodes.
-        (!antenna1_is_right, !antenna1_is_below),
-        find_harmonics,
-    );
-    antinode1.append(&mut antinode2);
-
-    // If we are searching for harmonics, there will always be a harmonic at the antenna positions.
-    if find_harmonics {
-        antinode1.push(position1);
-        antinode1.push(position2);
+) -> Vec<Vector> {
+    let difference = antenna1 - antenna2;
+
+    let mut antinodes = vec![];
+    for (antenna, step) in [(antenna1, difference), (antenna2, -difference)] {
+        let mut steps = if find_harmonics { 0 } else { 1 };
+        loop {
+            let antinode = antenna + step * steps;
+            if !Point::try_from(antinode).is_ok_and(|point| grid.contains(point)) {
+                break;
+            }
+            antinodes.push(antinode);
+            if !find_harmonics {
+                break;
+            }
+            steps += 1;
+        }
     }
 
-    return antinode1;
+    return antinodes;
 }
 
-fn count_antinodes(grid: &Vec<Vec<char>>, find_harmonics: bool) -> usize {
-    // Determine the size of the grid.
-    let grid_size_iterator = grid.iter();
-    let size = (grid_size_iterator.len(), grid.iter().next().unwrap().len());
-
+fn find_antinodes(grid: &Grid<char>, find_harmonics: bool) -> SparseGrid<char> {
     let mut map = HashMap::new();
 
     // Get the coordinates of every antenna.
-    grid.iter().enumerate().for_each(|(row_i, columns)| {
-        columns.iter().enumerate().for_each(|(col_i, frequency)| {
-            // Skip any empty spaces.
-            if frequency == &'.' {
-                return;
-            }
+    grid.iter().for_each(|(point, &frequency)| {
+        // Skip any empty spaces.
+        if frequency == '.' {
+            return;
+        }
 
-            let antenna_locations = map.get_mut(frequency);
-            if antenna_locations.is_none() {
-                map.insert(frequency, vec![(row_i, col_i)]);
-            } else {
-                antenna_locations.unwrap().push((row_i, col_i));
-            }
-        })
+        let antenna = Vector::try_from(point).expect("The grid must fit in memory");
+        map.entry(frequency).or_insert(vec![]).push(antenna);
     });
 
-    // Store a unique set of all antinode locations. There can only be one antinode
-    // at any given position, so this will allow us to exclude duplicates.
-    let mut unique_antinode_locations = HashSet::new();
+    // Mark every antinode location. There can only be one antinode at any given
+    // position, so this will allow us to exclude duplicates.
+    let mut antinodes = SparseGrid::new();
 
     map.iter().for_each(|(&_frequency, antenna_locations)| {
         // Loop through all antenna locations of a frequency and compare them to all remaining antenna locations
         for i in 0..antenna_locations.len() {
             for j in i + 1..antenna_locations.len() {
                 find_all_antinodes(
+                    grid,
                     antenna_locations[i],
                     antenna_locations[j],
-                    size,
                     find_harmonics,
                 )
                 .into_iter()
-                .for_each(|antinode_position| {
-                    unique_antinode_locations.insert(antinode_position);
+                .for_each(|antinode| {
+                    antinodes.insert(antinode, '#');
                 });
             }
         }
     });
 
-    return unique_antinode_locations.len();
+    return antinodes;
 }
 
-fn part1(grid: &Vec<Vec<char>>) -> usize {
-    return count_antinodes(&grid, false);
+fn part1(grid: &Grid<char>) -> usize {
+    return find_antinodes(grid, false).len();
+}
+fn part2(grid: &Grid<char>) -> usize {
+    return find_antinodes(grid, true).len();
 }
-fn part2(grid: &Vec<Vec<char>>) -> usize {
-    return count_antinodes(&grid, true);
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Grid<char>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    fn part1(grid: &Self::Input) -> usize {
+        return part1(grid);
+    }
+
+    fn part2(grid: &Self::Input) -> usize {
+        return part2(grid);
+    }
 }
 
-pub fn run() -> (usize, usize) {
-    let grid = process_file("input/year2024/day08.txt");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+";
+
+    #[test]
+    fn parse_example() {
+        let grid = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!((grid.row_count, grid.col_count), (12, 12));
+        assert_eq!(grid[Point::new(8, 1)], '0');
+    }
 
-    let part1_result = part1(&grid);
-    let part2_result = part2(&grid);
+    #[test]
+    fn part1_example() {
+        let grid = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&grid), 14);
+    }
 
-    println!("{},{}", part1_result, part2_result);
+    #[test]
+    fn draws_example_antinodes() {
+        let grid = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            find_antinodes(&grid, false).render('.'),
+            "\
+......#....#
+...#........
+....#.....#.
+..#.........
+.........#..
+.#....#.....
+...#........
+#......#....
+............
+............
+..........#.
+..........#.
+"
+        );
+    }
 
-    return (part1_result, part2_result);
+    #[test]
+    fn part2_example() {
+        let grid = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&grid), 34);
+    }
 }
@@ -1,13 +1,20 @@
-use crate::util::file::read;
+use crate::error::{self, Error};
+use crate::solution::Solution;
 
-fn process_file(filename: &str) -> Vec<u32> {
-    return read(filename)
-        .unwrap()
-        .flatten()
+fn process_file(input: &str) -> Result<Vec<u32>, Error> {
+    let line = error::numbered(input)
         .next()
-        .unwrap()
+        .ok_or_else(|| Error::Input("expected a disk map but the input is empty".to_string()))?;
+
+    return line
+        .text
         .chars()
-        .map(|number| number.to_digit(10).unwrap())
+        .enumerate()
+        .map(|(index, number)| {
+            return number.to_digit(10).ok_or_else(|| {
+                line.error_at_char(index, format!("expected a digit but got '{number}'"))
+            });
+        })
         .collect();
 }
 
@@ -38,7 +45,7 @@ fn build_memory(disk_map: Vec<u32>) -> Vec<Option<usize>> {
     return memory;
 }
 
-fn calculate_checksum(memory: &Vec<Option<usize>>) -> usize {
+fn calculate_checksum(memory: &[Option<usize>]) -> usize {
     return memory
         .iter()
         .enumerate()
@@ -52,17 +59,23 @@ fn calculate_checksum(memory: &Vec<Option<usize>>) -> usize {
         .sum();
 }
 
-fn part1(mut memory: Vec<Option<usize>>) -> usize {
+fn part1(memory: &[Option<usize>]) -> usize {
+    // An empty disk has nothing to compact.
+    if memory.is_empty() {
+        return 0;
+    }
+    let mut memory = memory.to_vec();
     let mut free_memory_index = 0;
     let mut file_block_index = memory.len() - 1;
 
     loop {
-        // Iterate until a free memory index is found.
-        while memory[free_memory_index].is_some() {
+        // Iterate until a free memory index is found, stopping at the file blocks
+        // so a disk without free memory isn't read past its end.
+        while free_memory_index < file_block_index && memory[free_memory_index].is_some() {
             free_memory_index += 1;
         }
         // Iterate until a file block index is found.
-        while memory[file_block_index].is_none() {
+        while file_block_index > free_memory_index && memory[file_block_index].is_none() {
             file_block_index -= 1;
         }
         if file_block_index <= free_memory_index {
@@ -74,7 +87,12 @@ fn part1(mut memory: Vec<Option<usize>>) -> usize {
     return calculate_checksum(&memory);
 }
 
-fn part2(mut memory: Vec<Option<usize>>) -> usize {
+fn part2(memory: &[Option<usize>]) -> usize {
+    // The search for the last file below would run off the start of a disk without files.
+    if memory.iter().all(Option::is_none) {
+        return 0;
+    }
+    let mut memory = memory.to_vec();
     let mut file_block_index = memory.len() - 1;
 
     // Each interation of the loop should attempt to move a file block to a
@@ -105,14 +123,15 @@ fn part2(mut memory: Vec<Option<usize>>) -> usize {
         // Try to find available free memory space
         loop {
             // Skip memory that has values to try and find available empty memory.
-            while memory[free_memory_index].is_some() {
+            while free_memory_index <= file_block_first_index && memory[free_memory_index].is_some()
+            {
                 free_memory_index += 1;
                 free_memory_last_index = free_memory_index;
             }
 
             // If we start looking at free memory after the current file blocks index,
             // we know there isn't an availble spot and can return early.
-            if free_memory_last_index - 1 > file_block_first_index {
+            if free_memory_index > file_block_first_index {
                 free_memory_index = 0;
                 free_memory_last_index = 0;
                 break;
@@ -150,15 +169,126 @@ fn part2(mut memory: Vec<Option<usize>>) -> usize {
     return calculate_checksum(&memory);
 }
 
-pub fn run() -> (usize, usize) {
-    let disk_map = process_file("input/year2024/day09.txt");
-    let memory = build_memory(disk_map);
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Option<usize>>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let checksum1 = part1(memory.clone());
-    let checksum2 = part2(memory);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let disk_map = process_file(input)?;
+        return Ok(build_memory(disk_map));
+    }
+
+    fn part1(memory: &Self::Input) -> usize {
+        return part1(memory);
+    }
 
-    println!("Part1: {:?}", checksum1);
-    println!("Part2: {:?}", checksum2);
+    fn part2(memory: &Self::Input) -> usize {
+        return part2(memory);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::year2024::disk_map;
+    use crate::util::random::Rng;
+
+    const EXAMPLE: &str = "2333133121414131402\n";
+
+    #[test]
+    fn parse_expands_the_disk_map() {
+        let memory = Solver::parse("12345\n").unwrap();
+        let blocks: String = memory
+            .iter()
+            .map(|block| block.map_or('.', |id| char::from_digit(id as u32, 10).unwrap()))
+            .collect();
+        assert_eq!(blocks, "0..111....22222");
+    }
+
+    #[test]
+    fn disks_without_files_have_no_checksum() {
+        for input in ["\n", "0\n", "01\n", "0303\n"] {
+            let memory = Solver::parse(input).unwrap();
+            assert_eq!(Solver::part1(&memory), 0, "{input:?}");
+            assert_eq!(Solver::part2(&memory), 0, "{input:?}");
+        }
+    }
 
-    return (checksum1, checksum2);
+    #[test]
+    fn part1_example() {
+        let memory = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&memory), 1928);
+    }
+
+    #[test]
+    fn part2_example() {
+        let memory = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&memory), 2858);
+    }
+
+    // Moves whole files the same way as part 2, but keeps the disk as lists of file and
+    // free spans rather than as blocks.
+    fn compact_files(disk_map: &str) -> usize {
+        let mut files = vec![];
+        let mut free = vec![];
+        let mut position = 0;
+        for (index, size) in disk_map.trim().chars().enumerate() {
+            let size = size.to_digit(10).unwrap() as usize;
+            if index % 2 == 0 {
+                files.push((position, size));
+            } else {
+                free.push((position, size));
+            }
+            position += size;
+        }
+
+        for (start, size) in files.iter_mut().rev() {
+            let space = free
+                .iter_mut()
+                .take_while(|(free_start, _)| free_start < start)
+                .find(|(_, free_size)| free_size >= size);
+            if let Some((free_start, free_size)) = space {
+                *start = *free_start;
+                *free_start += *size;
+                *free_size -= *size;
+            }
+        }
+
+        return files
+            .iter()
+            .enumerate()
+            .map(|(id, &(start, size))| (start..start + size).sum::<usize>() * id)
+            .sum();
+    }
+
+    // Moving single blocks and moving whole files are the same when every file is a
+    // single block.
+    #[test]
+    fn compaction_agrees_for_single_block_files() {
+        for seed in 0..200 {
+            let mut rng = Rng::new(seed);
+            let files = rng.between(1, 30);
+            let disk_map = disk_map(&mut rng, files, 1);
+            let memory = Solver::parse(&disk_map).unwrap();
+            assert_eq!(Solver::part1(&memory), Solver::part2(&memory), "{disk_map}");
+        }
+    }
+
+    #[test]
+    fn part2_matches_moving_spans() {
+        for seed in 0..200 {
+            let mut rng = Rng::new(seed);
+            let files = rng.between(1, 30);
+            let disk_map = disk_map(&mut rng, files, 9);
+            let memory = Solver::parse(&disk_map).unwrap();
+            assert_eq!(
+                Solver::part2(&memory),
+                compact_files(&disk_map),
+                "{disk_map}"
+            );
+        }
+    }
 }
@@ -1,49 +1,38 @@
-use crate::util::file::read;
+use crate::error::{self, Error};
+use crate::solution::Solution;
+use crate::util::{grid::Grid, point::Point};
 use std::collections::HashSet;
 
-fn process_file(filename: &str) -> Vec<Vec<u32>> {
-    return read(filename)
-        .unwrap()
-        .flatten()
-        .map(|row| {
-            row.chars()
-                .map(|height| height.to_digit(10).unwrap())
-                .collect()
-        })
-        .collect();
+fn process_file(input: &str) -> Result<Grid<u32>, Error> {
+    return Grid::from_lines(error::numbered(input), |height| {
+        return height
+            .to_digit(10)
+            .ok_or_else(|| format!("expected a height but got '{height}'"));
+    });
 }
 
-fn find_trailheads(topographical_map: &Vec<Vec<u32>>) -> Vec<(usize, usize)> {
-    let mut trailheads = vec![];
-
-    topographical_map
+fn find_trailheads(topographical_map: &Grid<u32>) -> Vec<Point> {
+    return topographical_map
         .iter()
-        .enumerate()
-        .for_each(|(row, columns)| {
-            columns.iter().enumerate().for_each(|(col, &value)| {
-                if value == 0 {
-                    trailheads.push((row, col));
-                }
-            })
-        });
-
-    return trailheads;
+        .filter(|(_, &height)| height == 0)
+        .map(|(point, _)| point)
+        .collect();
 }
 
 fn determine_trailhead_score(
-    (row, col): (usize, usize),
-    topographical_map: &Vec<Vec<u32>>,
+    position: Point,
+    topographical_map: &Grid<u32>,
     expected_height: u32,
-    traveled_positions: &mut HashSet<(usize, usize)>,
+    traveled_positions: &mut HashSet<Point>,
 ) -> (usize, usize) {
-    let current_height = topographical_map[row][col];
+    let current_height = topographical_map[position];
 
     if current_height != expected_height {
         return (0, 0);
     }
 
     if current_height == 9 {
-        let is_already_visited = traveled_positions.insert((row, col));
+        let is_already_visited = traveled_positions.insert(position);
         return (
             match is_already_visited {
                 true => 1,
@@ -55,27 +44,12 @@ fn determine_trailhead_score(
 
     let next_expected_height = expected_height + 1;
 
-    let mut adjacent_positions = vec![];
-
     // Recursively check the adjacent spaces, if they exist.
-    if row > 0 {
-        adjacent_positions.push((row - 1, col));
-    }
-    if col > 0 {
-        adjacent_positions.push((row, col - 1));
-    }
-    if row + 1 < topographical_map.len() {
-        adjacent_positions.push((row + 1, col));
-    }
-    if col + 1 < topographical_map[row].len() {
-        adjacent_positions.push((row, col + 1));
-    }
-
-    return adjacent_positions
-        .iter()
-        .map(|&position| {
+    return topographical_map
+        .neighbors4(position)
+        .map(|(adjacent, _)| {
             determine_trailhead_score(
-                position,
+                adjacent,
                 topographical_map,
                 next_expected_height,
                 traveled_positions,
@@ -86,10 +60,7 @@ fn determine_trailhead_score(
         });
 }
 
-fn compute_trails(
-    topographical_map: &Vec<Vec<u32>>,
-    trailheads: Vec<(usize, usize)>,
-) -> (usize, usize) {
+fn compute_trails(topographical_map: &Grid<u32>, trailheads: Vec<Point>) -> (usize, usize) {
     return trailheads
         .iter()
         .map(|&position| {
@@ -100,13 +71,109 @@ fn compute_trails(
         });
 }
 
-pub fn run() -> (usize, usize) {
-    let topographical_map = process_file("input/year2024/day10.txt");
-    let trailheads = find_trailheads(&topographical_map);
+pub struct Solver;
 
-    let (score, rating) = compute_trails(&topographical_map, trailheads);
+impl Solution for Solver {
+    type Input = Grid<u32>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    println!("{:?}", (score, rating));
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
 
-    return (score, rating);
+    // Sum of the trailhead scores
+    fn part1(topographical_map: &Self::Input) -> usize {
+        let trailheads = find_trailheads(topographical_map);
+        return compute_trails(topographical_map, trailheads).0;
+    }
+
+    // Sum of the trailhead ratings
+    fn part2(topographical_map: &Self::Input) -> usize {
+        let trailheads = find_trailheads(topographical_map);
+        return compute_trails(topographical_map, trailheads).1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::year2024::topographic_map;
+    use crate::util::random::Rng;
+
+    const EXAMPLE: &str = "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+";
+
+    #[test]
+    fn parse_example() {
+        let topographical_map = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(topographical_map.contents[..8], [8, 9, 0, 1, 0, 1, 2, 3]);
+        assert_eq!(find_trailheads(&topographical_map).len(), 9);
+    }
+
+    #[test]
+    fn part1_example() {
+        let topographical_map = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&topographical_map), 36);
+    }
+
+    #[test]
+    fn part2_example() {
+        let topographical_map = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&topographical_map), 81);
+    }
+
+    // The scores and ratings of every position, working down from the 9s: the 9s a
+    // position reaches and how many trails get there.
+    fn scores_and_ratings(map: &Grid<u32>) -> (usize, usize) {
+        let mut peaks: Grid<HashSet<Point>> = map.map(|_| HashSet::new());
+        let mut trails: Grid<usize> = map.map(|_| 0);
+
+        for height in (0..=9).rev() {
+            for point in map.points().filter(|&point| map[point] == height) {
+                if height == 9 {
+                    peaks[point].insert(point);
+                    trails[point] = 1;
+                    continue;
+                }
+                for (next, _) in map
+                    .neighbors4(point)
+                    .filter(|&(_, &next)| next == height + 1)
+                {
+                    let reached = peaks[next].clone();
+                    peaks[point].extend(reached);
+                    trails[point] += trails[next];
+                }
+            }
+        }
+
+        return find_trailheads(map)
+            .iter()
+            .fold((0, 0), |(score, rating), &trailhead| {
+                return (score + peaks[trailhead].len(), rating + trails[trailhead]);
+            });
+    }
+
+    #[test]
+    fn trails_match_counting_from_the_peaks() {
+        for seed in 0..100 {
+            let mut rng = Rng::new(seed);
+            let (row_count, col_count) = (rng.between(1, 12), rng.between(1, 12));
+            let map = topographic_map(&mut rng, row_count, col_count);
+
+            let (score, rating) = scores_and_ratings(&map);
+            assert_eq!(Solver::part1(&map), score, "{map}");
+            assert_eq!(Solver::part2(&map), rating, "{map}");
+            // Every 9 a trailhead reaches has at least one trail to it.
+            assert!(score <= rating);
+        }
+    }
 }
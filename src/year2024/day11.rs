@@ -1,16 +1,17 @@
-use crate::util::file::read;
+use crate::error::{self, Error};
+use crate::solution::Solution;
 use std::collections::HashMap;
 
-fn process_file(filename: &str) -> Vec<u64> {
-    read(filename)
-        .unwrap()
-        .into_iter()
-        .flatten()
-        .next()
-        .unwrap()
+fn process_file(input: &str) -> Result<Vec<u64>, Error> {
+    let line = error::numbered(input).next().ok_or_else(|| {
+        Error::Input("expected a row of stones but the input is empty".to_string())
+    })?;
+
+    return line
+        .text
         .split(" ")
-        .map(|num| num.parse::<u64>().unwrap())
-        .collect::<Vec<u64>>()
+        .map(|num| line.parse::<u64>(num))
+        .collect::<Result<Vec<u64>, Error>>();
 }
 
 #[derive(Eq, PartialEq, Hash)]
@@ -31,14 +32,13 @@ fn blink(
     }
 
     let compute_rock_key = ComputedRock {
-        iteration: iteration,
-        rock_number: rock_number,
+        iteration,
+        rock_number,
     };
 
     // Check the computation map to see if this value has already been computed.
-    let computed_value = computation_map.get(&compute_rock_key);
-    if computed_value.is_some() {
-        return *computed_value.unwrap();
+    if let Some(&computed_value) = computation_map.get(&compute_rock_key) {
+        return computed_value;
     }
 
     let rock_number_string = rock_number.to_string();
@@ -47,7 +47,7 @@ fn blink(
     let computed_count = if rock_number == 0 {
         // If the rock is 0, set it to 10.
         blink(1, iteration + 1, max_iteration, computation_map)
-    } else if number_length % 2 == 0 {
+    } else if number_length.is_multiple_of(2) {
         // If the number has an even number of digits, split the rock into two different rocks.
         blink(
             rock_number_string[0..(number_length / 2)]
@@ -79,7 +79,7 @@ fn blink(
     return computed_count;
 }
 
-fn part1(rocks: &Vec<u64>) -> u64 {
+fn part1(rocks: &[u64]) -> u64 {
     let mut computation_map: HashMap<ComputedRock, u64> = HashMap::new();
 
     return rocks
@@ -90,7 +90,7 @@ fn part1(rocks: &Vec<u64>) -> u64 {
         .sum();
 }
 
-fn part2(rocks: &Vec<u64>) -> u64 {
+fn part2(rocks: &[u64]) -> u64 {
     let mut computation_map: HashMap<ComputedRock, u64> = HashMap::new();
 
     return rocks
@@ -101,12 +101,56 @@ fn part2(rocks: &Vec<u64>) -> u64 {
         .sum();
 }
 
-pub fn run() {
-    let rocks = process_file("input/year2024/day11.txt");
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<u64>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    fn part1(rocks: &Self::Input) -> u64 {
+        return part1(rocks);
+    }
+
+    fn part2(rocks: &Self::Input) -> u64 {
+        return part2(rocks);
+    }
+}
 
-    let blinks_25 = part1(&rocks);
-    let blinks_75 = part2(&rocks);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    println!("{:?}", blinks_25);
-    println!("{:?}", blinks_75);
+    const EXAMPLE: &str = "125 17\n";
+
+    #[test]
+    fn parse_example() {
+        assert_eq!(Solver::parse(EXAMPLE).unwrap(), [125, 17]);
+    }
+
+    #[test]
+    fn blink_six_times() {
+        let mut computation_map = HashMap::new();
+        let stones: u64 = [125, 17]
+            .iter()
+            .map(|&rock_number| blink(rock_number, 0, 6, &mut computation_map))
+            .sum();
+        assert_eq!(stones, 22);
+    }
+
+    #[test]
+    fn part1_example() {
+        let rocks = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&rocks), 55312);
+    }
+
+    #[test]
+    fn part2_example() {
+        let rocks = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&rocks), 65601038650482);
+    }
 }
@@ -1,57 +1,95 @@
-use crate::util::file::read;
+use crate::error::Error;
+use crate::solution::Solution;
+use crate::util::file;
 
-fn process_line(input_string: &str) -> (i64, i64) {
-    // Grab ["X+DD", "Y+DD"]
-    let mut movement_string = input_string.split(": ").last().unwrap().split(", ");
-
-    return (
-        movement_string.next().unwrap()[2..].parse::<i64>().unwrap(),
-        movement_string.next().unwrap()[2..].parse::<i64>().unwrap(),
-    );
-}
-
-fn process_file(filename: &str) -> Vec<Matrix> {
-    let mut input_iter = read(filename).unwrap().flatten();
-
-    let mut matrixes = vec![];
-
-    loop {
-        let input_a = input_iter.next();
+fn process_file(input: &str) -> Result<Vec<Matrix>, Error> {
+    return file::sections(input)
+        .iter()
+        .map(|machine| {
+            // Each machine is "Button A: X+94, Y+34", "Button B: ..." and "Prize: X=8400, Y=5400".
+            let [button_a, button_b, prize] = &machine[..] else {
+                return Err(machine[0].error("expected two buttons and a prize location"));
+            };
 
-        // If there is no input, it means the end of the file is found.
-        if input_a.is_none() {
-            break;
-        }
+            let [a_x, a_y] = file::ints_array::<i64, 2>(button_a, "an X and a Y value")?;
+            let [b_x, b_y] = file::ints_array::<i64, 2>(button_b, "an X and a Y value")?;
+            let [prize_x, prize_y] = file::ints_array::<i64, 2>(prize, "an X and a Y value")?;
 
-        let a = process_line(&input_a.unwrap());
-        let b = process_line(&input_iter.next().unwrap());
-        let prize = process_line(&input_iter.next().unwrap());
+            return Ok([[a_x, b_x, prize_x], [a_y, b_y, prize_y]]);
+        })
+        .collect();
+}
 
-        matrixes.push([[a.0, b.0, prize.0], [a.1, b.1, prize.1]]);
+type Matrix = [[i64; COL_SIZE]; ROW_SIZE];
+const ROW_SIZE: usize = 2;
+const COL_SIZE: usize = 3;
 
-        // Skip over the empty line
-        input_iter.next();
+// The cheapest way to travel `target` along one axis when A moves `a_step` and B moves
+// `b_step`, as the number of presses of each button.
+fn cheapest_presses(a_step: i64, b_step: i64, target: i64) -> Option<(i64, i64)> {
+    // With a button that doesn't move, only the other one can be pressed.
+    let presses = |step: i64| (target % step == 0 && target / step >= 0).then(|| target / step);
+    match (a_step, b_step) {
+        (0, 0) => return (target == 0).then_some((0, 0)),
+        (0, _) => return presses(b_step).map(|b| (0, b)),
+        (_, 0) => return presses(a_step).map(|a| (a, 0)),
+        _ => {}
     }
 
-    return matrixes;
+    // Every solution is found from another by trading presses of one button for the
+    // other, and the cost changes by the same amount with each trade. So the cheapest is
+    // either the fewest presses of A or the fewest presses of B. The fewest presses of a
+    // button is always less than the step of the other one.
+    let fewest_a = (0..b_step)
+        .map(|a| (a, target - a * a_step))
+        .find(|&(_, rest)| rest >= 0 && rest % b_step == 0)
+        .map(|(a, rest)| (a, rest / b_step));
+    let fewest_b = (0..a_step)
+        .map(|b| (b, target - b * b_step))
+        .find(|&(_, rest)| rest >= 0 && rest % a_step == 0)
+        .map(|(b, rest)| (rest / a_step, b));
+    return [fewest_a, fewest_b]
+        .into_iter()
+        .flatten()
+        .min_by_key(|(a, b)| a * 3 + b);
 }
 
-type Matrix = [[i64; COL_SIZE]; ROW_SIZE];
-const ROW_SIZE: usize = 2;
-const COL_SIZE: usize = 3;
+// When both buttons move the claw along the same line there is no single solution, so
+// solve along whichever axis the buttons move in and check the other axis agrees.
+fn collinear_presses(matrix: Matrix) -> Option<i64> {
+    let [axis, other] = if matrix[0][0] == 0 && matrix[0][1] == 0 {
+        [matrix[1], matrix[0]]
+    } else {
+        matrix
+    };
+    let (a, b) = cheapest_presses(axis[0], axis[1], axis[2])?;
+    if other[0] * a + other[1] * b != other[2] {
+        return None;
+    }
+    return Some(a * 3 + b);
+}
 
 fn gaussian_elimination(mut matrix: Matrix) -> Option<i64> {
+    if matrix[0][0] * matrix[1][1] == matrix[0][1] * matrix[1][0] {
+        return collinear_presses(matrix);
+    }
+
+    // Button A moves along at least one axis, so make that the first row.
+    if matrix[0][0] == 0 {
+        matrix.swap(0, 1);
+    }
+    let original = matrix;
+
     // Set the values in the first column to be equal using the least common multiple.
     let multiply_by = [matrix[1][0], matrix[0][0]];
     for row in 0..ROW_SIZE {
-        for col in 0..COL_SIZE {
-            matrix[row][col] *= multiply_by[row];
-        }
+        matrix[row] = matrix[row].map(|value| value * multiply_by[row]);
     }
 
     // R2 - R1 to put a 0 in the bottom left corner.
-    for col in 0..COL_SIZE {
-        matrix[1][col] -= matrix[0][col];
+    let first_row = matrix[0];
+    for (value, first_row_value) in matrix[1].iter_mut().zip(first_row) {
+        *value -= first_row_value;
     }
 
     // Finally, use back substituion to determine the values of A and B.
@@ -60,22 +98,30 @@ fn gaussian_elimination(mut matrix: Matrix) -> Option<i64> {
         return None;
     }
     let b = matrix[1][2] / matrix[1][1];
-    if (matrix[0][2] - matrix[0][1] * b) % matrix[0][0] != 0 {
+    // Button A might not move along the second axis, which clears the scaled first row,
+    // so substitute into the original one.
+    let first_row = original[0];
+    if (first_row[2] - first_row[1] * b) % first_row[0] != 0 {
+        return None;
+    }
+    let a = (first_row[2] - first_row[1] * b) / first_row[0];
+
+    // Buttons can't be pressed a negative number of times.
+    if a < 0 || b < 0 {
         return None;
     }
-    let a = (matrix[0][2] - matrix[0][1] * b) / matrix[0][0];
 
     return Some(a * 3 + b);
 }
 
-fn part1(matrixes: Vec<Matrix>) -> i64 {
+fn part1(matrixes: &[Matrix]) -> i64 {
     return matrixes
         .iter()
         .filter_map(|matrix| gaussian_elimination(*matrix))
         .sum();
 }
 
-fn part2(matrixes: Vec<Matrix>) -> i64 {
+fn part2(matrixes: &[Matrix]) -> i64 {
     return matrixes
         .iter()
         .map(|matrix| {
@@ -84,13 +130,104 @@ fn part2(matrixes: Vec<Matrix>) -> i64 {
                 return row;
             })
         })
-        .filter_map(|matrix| gaussian_elimination(matrix))
+        .filter_map(gaussian_elimination)
         .sum();
 }
 
-pub fn run() {
-    let inputs = process_file("input/year2024/day13.txt");
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Matrix>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    fn part1(matrixes: &Self::Input) -> i64 {
+        return part1(matrixes);
+    }
+
+    fn part2(matrixes: &Self::Input) -> i64 {
+        return part2(matrixes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+";
+
+    #[test]
+    fn parse_example() {
+        let matrixes = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(matrixes.len(), 4);
+        assert_eq!(matrixes[0], [[94, 22, 8400], [34, 67, 5400]]);
+    }
 
-    println!("Part 1: {:?}", part1(inputs.clone()));
-    println!("Part 2: {:?}", part2(inputs));
+    #[test]
+    fn gaussian_elimination_example() {
+        let matrixes = Solver::parse(EXAMPLE).unwrap();
+        let tokens: Vec<Option<i64>> = matrixes.into_iter().map(gaussian_elimination).collect();
+        assert_eq!(tokens, [Some(280), None, Some(200), None]);
+    }
+
+    #[test]
+    fn gaussian_elimination_rejects_negative_presses() {
+        // The prize is reached by pressing A -1 times and B twice.
+        assert_eq!(gaussian_elimination([[10, 20, 30], [20, 10, 0]]), None);
+    }
+
+    #[test]
+    fn gaussian_elimination_handles_buttons_along_one_axis() {
+        // A only moves along Y and B only along X, so the rows have zeros in them.
+        assert_eq!(gaussian_elimination([[0, 2, 6], [5, 0, 10]]), Some(9));
+        assert_eq!(gaussian_elimination([[3, 0, 6], [0, 4, 12]]), Some(9));
+    }
+
+    #[test]
+    fn gaussian_elimination_handles_collinear_buttons() {
+        // B moves twice as far as A, so one press of each is cheapest.
+        assert_eq!(gaussian_elimination([[1, 2, 3], [1, 2, 3]]), Some(4));
+        // A moves more than three times as far as B, so press A as much as possible.
+        assert_eq!(gaussian_elimination([[7, 2, 16], [7, 2, 16]]), Some(7));
+        // The buttons only move along Y.
+        assert_eq!(gaussian_elimination([[0, 0, 0], [1, 2, 3]]), Some(4));
+        // The prize is not on the line the buttons move along.
+        assert_eq!(gaussian_elimination([[1, 2, 3], [1, 2, 4]]), None);
+        // The prize is on the line but out of reach of whole presses.
+        assert_eq!(gaussian_elimination([[2, 4, 5], [2, 4, 5]]), None);
+        // Neither button moves the claw.
+        assert_eq!(gaussian_elimination([[0, 0, 1], [0, 0, 1]]), None);
+    }
+
+    #[test]
+    fn part1_example() {
+        let matrixes = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&matrixes), 480);
+    }
+
+    #[test]
+    fn part2_example() {
+        let matrixes = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&matrixes), 875318608908);
+    }
 }
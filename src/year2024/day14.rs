@@ -1,102 +1,148 @@
-use crate::util::file::read;
+use crate::error::{self, Error};
+use crate::solution::Solution;
+use crate::util::file;
+use crate::util::grid::Grid;
+use crate::util::point::{Point, Vector};
 
-type Input = Vec<((i32, i32), (i32, i32))>;
+// The position and velocity of every robot.
+type Input = Vec<(Vector, Vector)>;
+// The width and height of the space the robots patrol.
+type Area = (usize, usize);
 
-fn process_file(filename: &str) -> Input {
-    let input_vec = read(filename).unwrap().flatten().collect::<Vec<String>>();
-    return input_vec
-        .iter()
+fn process_file(input: &str) -> Result<Input, Error> {
+    return error::numbered(input)
         .map(|line| {
-            let mut split_input = line
-                // Split the position and velocity into separate iterators "p=9,5 v=-3,-3"
-                .split(' ')
-                // Grab the numbers from a string like "p=9,5" and "v=-3,-3"
-                .flat_map(|string| string[2..].split(','))
-                .map(|value| value.parse::<i32>().unwrap());
-            return (
-                (split_input.next().unwrap(), split_input.next().unwrap()),
-                (split_input.next().unwrap(), split_input.next().unwrap()),
-            );
+            // A robot looks like "p=9,5 v=-3,-3".
+            let [x, y, dx, dy] = file::ints_array(&line, "a position and a velocity")?;
+            return Ok((Vector::new(x, y), Vector::new(dx, dy)));
         })
         .collect();
 }
 
-// Determine the density of robots per quadrant at a given second.
-fn quadrant_density(input: Input, second: i32) -> (i32, i32, i32, i32) {
-    return input
-        .into_iter()
-        .map(|mut robot| {
-            // Move the security robots through their total path.
-            robot.0 .0 += robot.1 .0 * second;
-            robot.0 .1 += robot.1 .1 * second;
-            return robot;
-        })
-        .map(|mut robot| {
-            // Handle robots teleporting to the other side when going out of bounds.
-            if robot.0 .0 < 0 {
-                robot.0 .0 += (-robot.0 .0 / WIDTH + 1) * (WIDTH);
-            }
-            if robot.0 .0 >= WIDTH {
-                robot.0 .0 %= WIDTH;
-            }
-
-            if robot.0 .1 < 0 {
-                robot.0 .1 += (-robot.0 .1 / HEIGHT + 1) * (HEIGHT);
-            }
-            if robot.0 .1 >= HEIGHT {
-                robot.0 .1 %= HEIGHT;
-            }
-
-            return robot;
-        })
-        .fold((0, 0, 0, 0), |mut quadrant, robot| {
-            let quadrant_width = WIDTH / 2;
-            let quadrant_height = HEIGHT / 2;
-            if robot.0 .0 > quadrant_width && robot.0 .1 > quadrant_height {
-                quadrant.0 += 1;
-            } else if robot.0 .0 > quadrant_width && robot.0 .1 < quadrant_height {
-                quadrant.1 += 1;
-            } else if robot.0 .0 < quadrant_width && robot.0 .1 < quadrant_height {
-                quadrant.2 += 1;
-            } else if robot.0 .0 < quadrant_width && robot.0 .1 > quadrant_height {
-                quadrant.3 += 1;
-            }
-            return quadrant;
-        });
+// Count the robots on every tile of the bathroom at a given second.
+fn robot_counts(input: &Input, second: isize, area: Area) -> Grid<usize> {
+    let (width, height) = area;
+    let mut tiles = Grid::new_filled(height, width, 0);
+
+    // Move the security robots through their total path. Robots teleport to the other
+    // side when they go out of bounds.
+    for &(position, velocity) in input {
+        let tile = tiles.wrap(position + velocity * second);
+        tiles[tile] += 1;
+    }
+
+    return tiles;
 }
 
-const NUMBER_OF_SECONDS: i32 = 100;
-const WIDTH: i32 = 101;
-const HEIGHT: i32 = 103;
+// Determine the number of robots in each quadrant at a given second. Robots exactly in
+// the middle of the bathroom aren't in any quadrant.
+fn quadrant_density(input: &Input, second: isize, area: Area) -> [usize; 4] {
+    let tiles = robot_counts(input, second, area);
+    let (width, height) = (area.0 / 2, area.1 / 2);
+    let (right, bottom) = (area.0 - width, area.1 - height);
 
-fn part1(input: Input) -> i32 {
-    let quadrants = quadrant_density(input, NUMBER_OF_SECONDS);
-    return quadrants.0 * quadrants.1 * quadrants.2 * quadrants.3;
+    return [
+        Point::new(0, 0),
+        Point::new(right, 0),
+        Point::new(0, bottom),
+        Point::new(right, bottom),
+    ]
+    .map(|top_left| {
+        return tiles
+            .window(top_left, width, height)
+            .expect("Quadrants are inside the bathroom")
+            .rows()
+            .flatten()
+            .sum();
+    });
 }
 
-const THRESHOLD: i32 = 310;
+const NUMBER_OF_SECONDS: isize = 100;
+// The size of the real bathroom.
+const BATHROOM: Area = (101, 103);
+
+fn part1(input: &Input, area: Area) -> usize {
+    return quadrant_density(input, NUMBER_OF_SECONDS, area)
+        .iter()
+        .product();
+}
+
+const THRESHOLD: usize = 310;
 
 // Part 2 operates on the assumption that the christmas tree formed by the robot
 // locations can be found using the density of robots in quadrants.
-fn part2(input: Input) -> i32 {
+fn part2(input: &Input, area: Area) -> isize {
     let mut seconds = 0;
     loop {
-        let quadrants = quadrant_density(input.clone(), seconds);
+        let quadrants = quadrant_density(input, seconds, area);
         // If any quadrant has a high density of robots, assume it's the Christmas tree.
-        if quadrants.0 > THRESHOLD
-            || quadrants.1 > THRESHOLD
-            || quadrants.2 > THRESHOLD
-            || quadrants.3 > THRESHOLD
-        {
+        if quadrants.iter().any(|&robots| robots > THRESHOLD) {
             return seconds;
         }
         seconds += 1;
     }
 }
 
-pub fn run() {
-    let input = process_file("input/year2024/day14.txt");
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Input;
+    type Part1 = usize;
+    type Part2 = isize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    fn part1(input: &Self::Input) -> usize {
+        return part1(input, BATHROOM);
+    }
+
+    fn part2(input: &Self::Input) -> isize {
+        return part2(input, BATHROOM);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+";
 
-    println!("Part 1: {:?}", part1(input.clone()));
-    println!("Part 2: {:?}", part2(input));
+    // The example robots patrol a smaller space than the real bathroom.
+    const EXAMPLE_AREA: Area = (11, 7);
+
+    #[test]
+    fn parse_example() {
+        let robots = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(robots.len(), 12);
+        assert_eq!(robots[11], (Vector::new(9, 5), Vector::new(-3, -3)));
+    }
+
+    #[test]
+    fn part1_example() {
+        let robots = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&robots, EXAMPLE_AREA), 12);
+    }
+
+    // The example has no Christmas tree, so crowd enough robots into one quadrant
+    // after a single second instead.
+    #[test]
+    fn part2_finds_a_crowded_quadrant() {
+        let robots = vec![(Vector::new(50, 0), Vector::new(1, 0)); THRESHOLD + 1];
+        assert_eq!(part2(&robots, BATHROOM), 1);
+    }
 }
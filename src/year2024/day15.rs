@@ -1,73 +1,51 @@
 use std::collections::{HashSet, VecDeque};
 
-use crate::util::{file::read, grid::Grid, point::Point};
+use crate::error::Error;
+use crate::solution::Solution;
+use crate::util::{direction::Direction, file, grid::Grid, point::Point};
 
 type Input = (Grid<char>, Vec<Direction>);
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum Direction {
-    UP,
-    DOWN,
-    LEFT,
-    RIGHT,
-}
-
-fn process_file(filename: &str) -> Input {
-    let mut input = read(filename).unwrap().flatten().collect::<Vec<String>>();
-
-    let input_split_index = input
-        .iter()
-        .enumerate()
-        .find(|(_, line)| line == &"")
-        .unwrap()
-        .0;
+fn process_file(input: &str) -> Result<Input, Error> {
+    let (map, instructions) = file::two_sections(input, "the warehouse map", "the moves")?;
+
+    let mut directions = vec![];
+    for line in &instructions {
+        for (index, char) in line.text.chars().enumerate() {
+            directions.push(Direction::from_arrow(char).ok_or_else(|| {
+                return line.error_at_char(
+                    index,
+                    format!("expected a move '^', 'v', '>' or '<' but got '{char}'"),
+                );
+            })?);
+        }
+    }
 
-    let instructions = input.split_off(input_split_index);
+    let warehouse = Grid::from_chars(map)?;
+    if warehouse.find_index(|&char| char == '@').is_none() {
+        return Err(Error::Input(
+            "the warehouse map has no robot '@'".to_string(),
+        ));
+    }
 
-    return (
-        Grid {
-            contents: input.iter().flat_map(|line| line.chars()).collect(),
-            col_count: input[0].len(),
-            row_count: input.len(),
-        },
-        instructions
-            .iter()
-            .flat_map(|line| {
-                line.chars().map(|char| match char {
-                    '^' => Direction::UP,
-                    'v' => Direction::DOWN,
-                    '>' => Direction::RIGHT,
-                    '<' => Direction::LEFT,
-                    _ => {
-                        panic!("Direction should have been '^', 'v', '>', or '<', but got '{char}'")
-                    }
-                })
-            })
-            .collect(),
-    );
+    return Ok((warehouse, directions));
 }
 
-fn get_next_location((row, col): (usize, usize), direction: Direction) -> (usize, usize) {
-    return match direction {
-        Direction::UP => (row - 1, col),
-        Direction::DOWN => (row + 1, col),
-        Direction::LEFT => (row, col - 1),
-        Direction::RIGHT => (row, col + 1),
-    };
+// The warehouse is surrounded by walls, so a move never leaves it.
+fn get_next_location(location: Point, direction: Direction) -> Point {
+    return location
+        .step(direction)
+        .expect("The warehouse must be surrounded by walls");
 }
 
-fn attempt_item_push(
-    warehouse: &mut Grid<char>,
-    (row, col): (usize, usize),
-    direction: Direction,
-) -> (usize, usize) {
+fn attempt_item_push(warehouse: &mut Grid<char>, robot: Point, direction: Direction) -> Point {
     let mut visited = HashSet::new();
     let mut to_visit_queue = VecDeque::new();
     let mut swap_list = VecDeque::new();
 
-    to_visit_queue.push_back((row, col));
+    to_visit_queue.push_back(robot);
 
-    let is_vertical_shift = direction == Direction::UP || direction == Direction::DOWN;
+    let is_vertical_shift = direction.is_vertical();
 
     // Perform a breadth first search to validate if the box can be pushed.
     while let Some(location) = to_visit_queue.pop_front() {
@@ -76,13 +54,13 @@ fn attempt_item_push(
             continue;
         }
 
-        let next_location = get_next_location((location.0, location.1), direction);
+        let next_location = get_next_location(location, direction);
 
-        let next_location_value = warehouse[Point::new(next_location.1, next_location.0)];
+        let next_location_value = warehouse[next_location];
 
         // If a wall is hit, return the current location because no shifting occurs.
         if next_location_value == '#' {
-            return (row, col);
+            return robot;
         }
 
         // If a box is found, check if the box can be pushed.
@@ -93,12 +71,12 @@ fn attempt_item_push(
         swap_list.push_back((location, next_location));
 
         if is_vertical_shift {
-            let location_value = warehouse[Point::new(location.1, location.0)];
+            let location_value = warehouse[location];
             if location_value == '[' {
-                let adj_location = (location.0, location.1 + 1);
+                let adj_location = get_next_location(location, Direction::Right);
                 to_visit_queue.push_back(adj_location);
             } else if location_value == ']' {
-                let adj_location = (location.0, location.1 - 1);
+                let adj_location = get_next_location(location, Direction::Left);
                 to_visit_queue.push_back(adj_location);
             }
         }
@@ -107,19 +85,19 @@ fn attempt_item_push(
     // If the function doesn't return early, it means the boxes can successfully be pushed.
     // Go through the swap list and move each box.
     while let Some((location, next_location)) = swap_list.pop_back() {
-        let temp = warehouse[Point::new(location.1, location.0)];
-        warehouse[Point::new(location.1, location.0)] =
-            warehouse[Point::new(next_location.1, next_location.0)];
-        warehouse[Point::new(next_location.1, next_location.0)] = temp;
+        let temp = warehouse[location];
+        warehouse[location] = warehouse[next_location];
+        warehouse[next_location] = temp;
     }
 
-    return get_next_location((row, col), direction);
+    return get_next_location(robot, direction);
 }
 
 fn get_final_gps_cord_sum((mut warehouse, instructions): Input) -> u32 {
-    let mut current_location = warehouse
+    let (row, col) = warehouse
         .find_index(|char| char == &'@')
-        .expect("Warehouse grid must contain a starting location");
+        .expect("The parser checks for the robot");
+    let mut current_location = Point::new(col, row);
 
     instructions.iter().for_each(|&direction| {
         current_location = attempt_item_push(&mut warehouse, current_location, direction);
@@ -138,15 +116,13 @@ fn get_final_gps_cord_sum((mut warehouse, instructions): Input) -> u32 {
     return count;
 }
 
-pub fn run() {
-    let input_part1 = process_file("input/year2024/day15.txt");
-    // Create the input for part 2 which doubles the width of the warehouse.
-    let input_part2 = (
+// Create the input for part 2 which doubles the width of the warehouse.
+fn widen_warehouse((warehouse, instructions): &Input) -> Input {
+    return (
         Grid {
-            row_count: input_part1.0.row_count,
-            col_count: input_part1.0.col_count * 2,
-            contents: input_part1
-                .0
+            row_count: warehouse.row_count,
+            col_count: warehouse.col_count * 2,
+            contents: warehouse
                 .contents
                 .iter()
                 .flat_map(|char| match char {
@@ -156,10 +132,106 @@ pub fn run() {
                 })
                 .collect(),
         },
-        input_part1.1.clone(),
+        instructions.clone(),
     );
+}
+
+pub struct Solver;
 
-    println!("Part 1: {:?}", get_final_gps_cord_sum(input_part1));
+impl Solution for Solver {
+    type Input = Input;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
 
-    println!("Part 2: {:?}", get_final_gps_cord_sum(input_part2));
+    fn part1(input: &Self::Input) -> u32 {
+        return get_final_gps_cord_sum(input.clone());
+    }
+
+    fn part2(input: &Self::Input) -> u32 {
+        return get_final_gps_cord_sum(widen_warehouse(input));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_EXAMPLE: &str = "\
+########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<
+";
+
+    const EXAMPLE: &str = "\
+##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+";
+
+    #[test]
+    fn parse_example() {
+        let (warehouse, instructions) = Solver::parse(SMALL_EXAMPLE).unwrap();
+        assert_eq!((warehouse.row_count, warehouse.col_count), (8, 8));
+        assert_eq!(instructions.len(), 15);
+        assert_eq!(instructions[..2], [Direction::Left, Direction::Up]);
+    }
+
+    #[test]
+    fn part1_small_example() {
+        let input = Solver::parse(SMALL_EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&input), 2028);
+    }
+
+    #[test]
+    fn part1_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&input), 10092);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&input), 9021);
+    }
+
+    #[test]
+    fn parse_rejects_unknown_moves() {
+        let error = Solver::parse("#@#\n\n<X\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 2: expected a move '^', 'v', '>' or '<' but got 'X'"
+        );
+
+        let error = Solver::parse("#.#\n\n<\n").unwrap_err();
+        assert_eq!(error.to_string(), "the warehouse map has no robot '@'");
+    }
 }
@@ -1,238 +1,181 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap, HashSet},
-};
+use std::collections::HashSet;
 
-use crate::util::{file::read, grid::Grid, point::Point};
+use crate::error::{self, Error};
+use crate::solution::Solution;
+use crate::util::{direction::Direction, grid::Grid, point::Point, search};
 
-type Vertex = (usize, usize);
+type Vertex = Point;
 
 type Input = (Grid<char>, Direction, Vertex, Vertex);
 
-#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
-enum Direction {
-    UP,
-    DOWN,
-    LEFT,
-    RIGHT,
-}
-
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 struct Location {
     direction: Direction,
     vertex: Vertex,
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Visit {
-    location: Location,
-    distance: u32,
-}
-
-// Create a custom ordering function so that the BinaryHeap priority queue will reorder itself to prioritize the least expensive moves.
-impl Ord for Visit {
-    fn cmp(&self, other: &Self) -> Ordering {
-        (other.distance).cmp(&(&self.distance))
-    }
-}
-impl PartialOrd for Visit {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+// The maze is surrounded by walls, so a move never leaves it.
+fn get_next_location(vertex: Vertex, direction: Direction) -> Vertex {
+    return vertex
+        .step(direction)
+        .expect("The maze must be surrounded by walls");
 }
 
-fn rotate_90_degrees(direction: Direction) -> Direction {
-    match direction {
-        Direction::RIGHT => Direction::DOWN,
-        Direction::DOWN => Direction::LEFT,
-        Direction::LEFT => Direction::UP,
-        Direction::UP => Direction::RIGHT,
-    }
-}
-
-fn get_next_location((row, col): Vertex, direction: Direction) -> Vertex {
-    return match direction {
-        Direction::UP => (row - 1, col),
-        Direction::DOWN => (row + 1, col),
-        Direction::LEFT => (row, col - 1),
-        Direction::RIGHT => (row, col + 1),
-    };
-}
-
-fn get_adjacent_paths(maze: &Grid<char>, visit: Visit) -> Vec<Visit> {
-    let rotate_90 = rotate_90_degrees(visit.location.direction);
-    let rotate_180 = rotate_90_degrees(rotate_90);
-    let rotate_270 = rotate_90_degrees(rotate_180);
+// Moving forward costs 1 point and turning 90 degrees costs 1000 points. Turning is
+// only useful to move in the new direction, so both are done in one step.
+fn get_adjacent_paths(maze: &Grid<char>, location: Location) -> Vec<(Location, u32)> {
+    let rotate_90 = location.direction.clockwise();
+    let rotate_270 = location.direction.counter_clockwise();
 
     return vec![
-        Visit {
-            location: Location {
-                vertex: get_next_location(visit.location.vertex, visit.location.direction),
-                direction: visit.location.direction,
-            },
-            distance: visit.distance + 1,
-        },
-        Visit {
-            location: Location {
-                vertex: get_next_location(visit.location.vertex, rotate_90),
-                direction: rotate_90,
-            },
-            distance: visit.distance + 1001,
-        },
-        Visit {
-            location: Location {
-                vertex: get_next_location(visit.location.vertex, rotate_270),
-                direction: rotate_270,
-            },
-            distance: visit.distance + 1001,
-        },
+        (location.direction, 1),
+        (rotate_90, 1001),
+        (rotate_270, 1001),
     ]
     .into_iter()
-    .filter(|visit| maze[Point::new(visit.location.vertex.1, visit.location.vertex.0)] != '#')
+    .map(|(direction, cost)| {
+        let next = Location {
+            vertex: get_next_location(location.vertex, direction),
+            direction,
+        };
+        return (next, cost);
+    })
+    .filter(|(next, _)| maze[next.vertex] != '#')
     .collect();
 }
 
-fn process_file(filename: &str) -> Input {
-    let input = read(filename)
-        .expect("File must have some contents")
-        .flatten()
-        .collect::<Vec<String>>();
+fn process_file(input: &str) -> Result<Input, Error> {
+    let maze = Grid::from_chars(error::numbered(input))?;
 
-    let maze = Grid {
-        contents: input.iter().flat_map(|line| line.chars()).collect(),
-        col_count: input[0].len(),
-        row_count: input.len(),
+    // The starting direction is always to the right.
+    let start_direction = Direction::Right;
+
+    let find_tile = |tile: char, name: &str| {
+        return maze
+            .find_index(|&char| char == tile)
+            .map(|(row, col)| Point::new(col, row))
+            .ok_or_else(|| Error::Input(format!("the maze has no {name} tile '{tile}'")));
     };
+    let start_location = find_tile('S', "start")?;
+    let end_location = find_tile('E', "end")?;
+    return Ok((maze, start_direction, start_location, end_location));
+}
 
-    // The starting direction is always to the right.
-    let start_direction = Direction::RIGHT;
-
-    let start_location = maze
-        .find_index(|char| char == &'S')
-        .expect("Maze must have a start location");
-    let end_location = maze
-        .find_index(|char| char == &'E')
-        .expect("Maze must have an end location");
-    return (maze, start_direction, start_location, end_location);
+// Use Dikjstra's algorithm to find the lowest score to complete the maze and the
+// number of tiles on any of the routes with that score.
+fn solve((maze, direction, start_vertex, end_vertex): &Input) -> (u32, usize) {
+    let start = Location {
+        vertex: *start_vertex,
+        direction: *direction,
+    };
+    let search = search::dijkstra(
+        start,
+        |&location| get_adjacent_paths(maze, location),
+        |location| location.vertex == *end_vertex,
+    );
+
+    // The end can be reached facing different directions with the same score.
+    let unique_spaces: HashSet<Vertex> = search
+        .on_shortest_paths(search.goals.iter().copied())
+        .iter()
+        .map(|location| location.vertex)
+        .collect();
+
+    return (
+        search
+            .goal_distance()
+            .expect("Maze does not have a valid path to the end location"),
+        unique_spaces.len(),
+    );
 }
 
-// Use Dikjstra's algorithm to find the shortest route to complete the maze.
-fn solve((maze, direction, start_vertex, end_vertex): Input) -> (u32, usize) {
-    let mut distances: HashMap<Location, u32> = HashMap::new();
-    let mut visited: HashSet<Location> = HashSet::new();
-    let mut to_visit_queue: BinaryHeap<Visit> = BinaryHeap::new();
-
-    to_visit_queue.push(Visit {
-        location: Location {
-            vertex: start_vertex,
-            direction: direction,
-        },
-        distance: 0,
-    });
-
-    let mut minimum_distance = None;
-
-    // These variables are used later to determine the number of unique locations visited.
-    let mut reversed_graph: HashMap<Location, Vec<Location>> = HashMap::new();
-    let mut end_locations: HashSet<Location> = HashSet::new();
-
-    // Perform Dijkstra's algorithm to find the minimum distance and construct a reversed graph.
-    while let Some(visit) = to_visit_queue.pop() {
-        let current_location = visit.location;
-        let current_distance = visit.distance;
-
-        if !visited.insert(current_location) {
-            continue;
-        }
-
-        // If the end location is found, return early.
-        if current_location.vertex == end_vertex {
-            if minimum_distance == None {
-                minimum_distance = Some(current_distance);
-            }
-            end_locations.insert(current_location);
-            continue;
-        }
-
-        // Check the adjacent paths and add them to the priority queue, if necessary.
-        get_adjacent_paths(&maze, visit)
-            .into_iter()
-            .for_each(|new_visit| {
-                let current_cost = match distances.get(&new_visit.location) {
-                    Some(cost) => cost,
-                    // If there is no known distance, set the distance to MAX so our new distance
-                    // is always cheaper.
-                    None => &u32::MAX,
-                };
-
-                // If the route is a higher cost, skip checking it.
-                if &new_visit.distance > current_cost {
-                    return;
-                }
-
-                // If the distance is greater than the already discovered minimum distance, skip the rest of the checks.
-                if minimum_distance.is_some() && Some(new_visit.distance) > minimum_distance {
-                    return;
-                }
-
-                distances.insert(new_visit.location, new_visit.distance);
-                to_visit_queue.push(new_visit);
-
-                // Create a reversed graph so we can traverse the tree in reverse later.
-                reversed_graph
-                    .entry(new_visit.location)
-                    .or_insert_with(Vec::new)
-                    .push(visit.location);
-            });
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Input;
+    type Part1 = u32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    // Lowest score to reach the end
+    fn part1(input: &Self::Input) -> u32 {
+        return solve(input).0;
     }
 
-    // As part of Dijkstra's algorithm previously, a reversed graph was constructed.
-    // If traversed, this graph will only include have full paths that are also
-    // the shortest paths.
-    // We can use a DFS to determine the unique spaces that were visited.
-    fn dfs(
-        current_location: Location,
-        end_location: Vertex,
-        children: &HashMap<Location, Vec<Location>>,
-        current_path: &mut Vec<Vertex>,
-        unique_spaces: &mut HashSet<Vertex>,
-    ) {
-        let mut new_path = current_path.clone();
-        new_path.push(current_location.vertex);
-
-        if current_location.vertex == end_location {
-            for vertex in new_path {
-                unique_spaces.insert(vertex);
-            }
-            return;
-        }
-
-        if let Some(children_vec) = children.get(&current_location) {
-            for &child in children_vec {
-                dfs(child, end_location, children, &mut new_path, unique_spaces);
-            }
-        }
+    // Number of tiles that are part of at least one of the best paths
+    fn part2(input: &Self::Input) -> usize {
+        return solve(input).1;
     }
+}
 
-    let mut unique_spaces: HashSet<Vertex> = HashSet::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+";
+
+    const SECOND_EXAMPLE: &str = "\
+#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################
+";
+
+    #[test]
+    fn parse_example() {
+        let (_, direction, start, end) = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(direction, Direction::Right);
+        assert_eq!(start, Point::new(1, 13));
+        assert_eq!(end, Point::new(13, 1));
+    }
 
-    for end_location in end_locations {
-        dfs(
-            end_location,
-            start_vertex,
-            &reversed_graph,
-            &mut vec![],
-            &mut unique_spaces,
-        );
+    #[test]
+    fn part1_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&input), 7036);
+
+        let input = Solver::parse(SECOND_EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&input), 11048);
     }
 
-    return (
-        minimum_distance.expect("Maze does not have a valid path to the end location"),
-        unique_spaces.len(),
-    );
-}
+    #[test]
+    fn part2_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&input), 45);
 
-pub fn run() {
-    let input = process_file("input/year2024/day16.txt");
-    println!("{:?}", solve(input));
+        let input = Solver::parse(SECOND_EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&input), 64);
+    }
 }
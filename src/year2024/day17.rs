@@ -1,35 +1,56 @@
-use crate::util::file::read;
+use crate::error::{Error, Line};
+use crate::solution::Solution;
+use crate::util::file;
 
 type Registers = (u64, u64, u64);
 type Program = Vec<u64>;
 
-fn process_file(filename: &str) -> (Registers, Program) {
-    let mut input_iter = read(filename).unwrap().flatten();
+fn process_file(input: &str) -> Result<(Registers, Program), Error> {
+    let (registers, program_lines) = file::two_sections(input, "the registers", "the program")?;
 
-    fn parse_register(input_number: &str) -> u64 {
-        input_number.split(": ").collect::<Vec<&str>>()[1]
-            .parse::<u64>()
-            .expect("Input must have parsable number values.")
-    }
+    let [a, b, c] = &registers[..] else {
+        return Err(registers[0].error("expected registers A, B and C"));
+    };
+    let register = |line: &Line| -> Result<u64, Error> {
+        let [value] = file::ints_array(line, "a register value")?;
+        return Ok(value);
+    };
+    let (a, b, c) = (register(a)?, register(b)?, register(c)?);
 
-    let a = parse_register(&input_iter.next().expect("Register A must have a value"));
-    let b = parse_register(&input_iter.next().expect("Register B must have a value"));
-    let c = parse_register(&input_iter.next().expect("Register C must have a value"));
-    // Skip the empty line in the input
-    input_iter.next();
-    let program = input_iter
-        .next()
-        .expect("Program line must exist")
-        .split(": ")
-        .collect::<Vec<&str>>()[1]
+    let [line] = &program_lines[..] else {
+        return Err(program_lines[1].error("expected the program on a single line"));
+    };
+    let codes = line
+        .expect(line.text.split(": ").nth(1), "': ' followed by the program")?
         .split(',')
-        .map(|c| {
-            c.parse::<u64>()
-                .expect("Input must have parsable number values.")
-        })
-        .collect::<Vec<u64>>();
+        .collect::<Vec<&str>>();
+
+    let mut program = vec![];
+    for (index, code) in codes.iter().enumerate() {
+        let value = line.parse::<u64>(code)?;
+        if value > 7 {
+            return Err(line.error_at(code, format!("'{value}' is not a 3-bit number")));
+        }
+
+        // Combo operand 7 is reserved and doesn't appear in valid programs.
+        let is_operand = index % 2 == 1;
+        if is_operand && value == 7 && uses_combo_operand(program[index - 1]) {
+            return Err(line.error_at(code, "combo operand 7 is reserved"));
+        }
+        program.push(value);
+    }
+
+    if program.len() % 2 == 1 {
+        return Err(line.error("the last instruction is missing its operand"));
+    }
 
-    return ((a, b, c), program);
+    return Ok(((a, b, c), program));
+}
+
+// Whether the instruction reads a combo operand rather than a literal one.
+// bxl and jnz take literals and bxc ignores its operand.
+fn uses_combo_operand(instruction: u64) -> bool {
+    return !matches!(instruction, 1 | 3 | 4);
 }
 
 fn process_program(
@@ -41,7 +62,7 @@ fn process_program(
     while let Some(instruction) = program.get(instruction_pointer) {
         let next_operand = program[instruction_pointer + 1];
 
-        let operand = if instruction == &1 || instruction == &3 {
+        let operand = if !uses_combo_operand(*instruction) {
             next_operand
         } else {
             match next_operand {
@@ -57,11 +78,11 @@ fn process_program(
         match instruction {
             0 => {
                 // adv - A / 2^(combo)
-                register_a = register_a >> operand;
+                register_a >>= operand;
             }
             1 => {
                 //bxl - Bitwise XOR of register B and the literal operand
-                register_b = register_b ^ operand;
+                register_b ^= operand;
             }
             2 => {
                 //bst - Calculates the combo operand modulo 8. This stores only the lowest 3 bits.
@@ -77,7 +98,7 @@ fn process_program(
             }
             4 => {
                 // bxc - B XOR C
-                register_b = register_b ^ register_c;
+                register_b ^= register_c;
             }
             5 => {
                 // out - combo operand module 8
@@ -108,7 +129,8 @@ fn part1(input: (Registers, &Program)) -> String {
         .join(",");
 }
 
-fn part2(mut program: Program) -> u64 {
+fn part2(program: &Program) -> u64 {
+    let mut program = program.clone();
     let program_rev: Vec<u64> = program.clone().into_iter().rev().collect();
 
     // Pop off the instructions that loop to prevent running through the entire program
@@ -135,12 +157,14 @@ fn part2(mut program: Program) -> u64 {
             return (acc << 3) + value;
         });
 
-        let result: Vec<u64> = process_program(((((acc << 3) + i), 0, 0), &program));
+        let register_a = (acc << 3) + i;
+        let result: Vec<u64> = process_program(((register_a, 0, 0), &program));
 
         let expected_number = program_rev[stack_index];
 
         // If the returned number is the same as the expected program number, add it to the number stack.
-        if result[0] == expected_number {
+        // Register A can't start at zero, or the program would stop before the rest of the output.
+        if register_a != 0 && result[0] == expected_number {
             stack.push(i);
             i = 0;
             stack_index += 1;
@@ -155,12 +179,87 @@ fn part2(mut program: Program) -> u64 {
     });
 }
 
-pub fn run() {
-    let inputs = process_file("input/year2024/day17.txt");
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = (Registers, Program);
+    type Part1 = String;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    fn part1((registers, program): &Self::Input) -> String {
+        return part1((*registers, program));
+    }
+
+    // The lowest value of register A that makes the program output a copy of itself
+    fn part2((_, program): &Self::Input) -> u64 {
+        return part2(program);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    println!("Part 1: {:?}", part1((inputs.0, &inputs.1)));
+    const PART1_EXAMPLE: &str = "\
+Register A: 729
+Register B: 0
+Register C: 0
 
-    let part_2_register_a = part2(inputs.1);
+Program: 0,1,5,4,3,0
+";
+
+    const PART2_EXAMPLE: &str = "\
+Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0
+";
+
+    #[test]
+    fn parse_example() {
+        let (registers, program) = Solver::parse(PART1_EXAMPLE).unwrap();
+        assert_eq!(registers, (729, 0, 0));
+        assert_eq!(program, [0, 1, 5, 4, 3, 0]);
+    }
 
-    println!("Part 2: {}", part_2_register_a);
+    #[test]
+    fn part1_example() {
+        let input = Solver::parse(PART1_EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&input), "4,6,3,5,6,3,5,2,1,0");
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Solver::parse(PART2_EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&input), 117440);
+    }
+
+    #[test]
+    fn part2_needs_a_nonzero_leading_digit() {
+        // With both XOR constants equal, A = 0 already outputs the final 0, but the
+        // program would stop there instead of printing the rest of itself.
+        let program = "2,4,1,4,7,5,1,4,4,7,5,5,0,3,3,0";
+        let input = Solver::parse(&format!(
+            "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {program}\n"
+        ))
+        .unwrap();
+        let register_a = Solver::part2(&input);
+        assert_eq!(register_a, 156984410572290);
+        assert_eq!(part1(((register_a, 0, 0), &input.1)), program);
+    }
+
+    #[test]
+    fn parse_rejects_combo_operand_7() {
+        let error = Solver::parse("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,7\n")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 5, column 12: combo operand 7 is reserved"
+        );
+    }
 }
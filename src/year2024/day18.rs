@@ -1,152 +1,224 @@
-use crate::util::{file::read, grid::Grid, point::Point};
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashSet},
-};
-
-fn process_file(filename: &str) -> Vec<Location> {
-    return read(filename)
-        .unwrap()
-        .flatten()
+use crate::error::{self, Error};
+use crate::solution::Solution;
+use crate::util::{grid::Grid, point::Point, search};
+
+fn process_file(input: &str) -> Result<Vec<Location>, Error> {
+    return error::numbered(input)
         .map(|line| {
-            let mut split_line = line.split(",");
-
-            return (
-                split_line
-                    .next()
-                    .expect("Value must exist")
-                    .parse::<usize>()
-                    .expect("Value must be integer"),
-                split_line
-                    .next()
-                    .expect("Value must exist")
-                    .parse::<usize>()
-                    .expect("Value must be integer"),
-            );
+            let mut split_line = line.text.split(",");
+
+            let x = line.parse::<usize>(line.expect(split_line.next(), "an X coordinate")?)?;
+            let y = line.parse::<usize>(line.expect(split_line.next(), "a Y coordinate")?)?;
+            if x >= WIDTH || y >= HEIGHT {
+                return Err(line.error(format!(
+                    "{x},{y} is outside the {WIDTH}x{HEIGHT} memory space"
+                )));
+            }
+
+            return Ok((x, y));
         })
-        .collect::<Vec<Location>>();
+        .collect::<Result<Vec<Location>, Error>>();
 }
 
 type Location = (usize, usize);
 
-#[derive(Eq, PartialEq)]
-struct Visit {
-    location: Location,
-    distance: u32,
-}
-// Create a custom ordering function so that the BinaryHeap priority queue will reorder itself to prioritize the least expensive moves.
-impl Ord for Visit {
-    fn cmp(&self, other: &Self) -> Ordering {
-        (other.distance).cmp(&(&self.distance))
-    }
-}
-impl PartialOrd for Visit {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-fn bfs(mut grid: Grid<char>, bytes_locations: &Vec<Location>, fallen_bytes: usize) -> Option<u32> {
+// The fewest steps from the top left corner to the exit in the bottom right corner
+// after `fallen_bytes` bytes have corrupted the memory space.
+fn bfs(mut grid: Grid<char>, bytes_locations: &[Location], fallen_bytes: usize) -> Option<u32> {
     // Apply the fallen bytes to the grid.
-    for i in 0..fallen_bytes {
-        let (x, y) = bytes_locations[i];
+    for &(x, y) in bytes_locations.iter().take(fallen_bytes) {
         grid[Point { x, y }] = '#';
     }
 
-    let mut to_visit_queue: BinaryHeap<Visit> = BinaryHeap::new();
-    to_visit_queue.push(Visit {
-        location: (0, 0),
-        distance: 0,
-    });
-
-    let mut visited: HashSet<Location> = HashSet::new();
+    let start = Point::new(0, 0);
+    let exit = Point::new(grid.col_count - 1, grid.row_count - 1);
+    if grid[start] == '#' {
+        return None;
+    }
 
-    // Perform Dijkstra's algorithm.
-    while let Some(visit) = to_visit_queue.pop() {
-        let (x, y) = visit.location;
+    let search = search::bfs(
+        start,
+        |&point| {
+            return grid
+                .neighbors4(point)
+                .filter(|(_, &value)| value != '#')
+                .map(|(next, _)| next);
+        },
+        |&point| point == exit,
+    );
+
+    return search.goal_distance();
+}
 
-        if grid[Point { x, y }] == '#' {
-            continue;
-        }
+fn part1(grid: &Grid<char>, bytes_locations: &[Location], fallen_bytes: usize) -> u32 {
+    return bfs(grid.clone(), bytes_locations, fallen_bytes).expect("Part 1 must have a value.");
+}
 
-        if !visited.insert(visit.location) {
-            continue;
-        }
+// `fallen_bytes` is a count that's known to leave the exit reachable. `None` when the
+// exit is still reachable after every byte has fallen.
+fn part2(grid: &Grid<char>, bytes_locations: &[Location], fallen_bytes: usize) -> Option<Location> {
+    if bfs(grid.clone(), bytes_locations, bytes_locations.len()).is_some() {
+        return None;
+    }
 
-        if visit.location == (WIDTH - 1, HEIGHT - 1) {
-            return Some(visit.distance);
-        }
+    // Binary search for the fewest fallen bytes that block the exit. The exit is
+    // reachable after `lower` bytes and blocked after `upper` bytes.
+    let mut lower = fallen_bytes;
+    let mut upper = bytes_locations.len();
+    while upper - lower > 1 {
+        let i = lower + (upper - lower) / 2;
 
-        let next_distance = visit.distance + 1;
-        if x < WIDTH - 1 {
-            to_visit_queue.push(Visit {
-                location: (x + 1, y),
-                distance: next_distance,
-            });
-        }
-        if x > 0 {
-            to_visit_queue.push(Visit {
-                location: (x - 1, y),
-                distance: next_distance,
-            });
-        }
-        if y < HEIGHT - 1 {
-            to_visit_queue.push(Visit {
-                location: (x, y + 1),
-                distance: next_distance,
-            });
-        }
-        if y > 0 {
-            to_visit_queue.push(Visit {
-                location: (x, y - 1),
-                distance: next_distance,
-            });
-        }
+        match bfs(grid.clone(), bytes_locations, i) {
+            None => upper = i,
+            Some(_) => lower = i,
+        };
     }
 
-    return None;
+    // The last byte to fall is the one that blocked the exit.
+    return Some(bytes_locations[upper - 1]);
 }
 
-fn part1(grid: &Grid<char>, bytes_locations: &Vec<Location>) -> u32 {
-    return bfs(grid.clone(), bytes_locations, PART1_BYTE_COUNT)
-        .expect("Part 1 must have a value.");
+const WIDTH: usize = 71;
+const HEIGHT: usize = 71;
+const PART1_BYTE_COUNT: usize = 1024;
+
+fn empty_memory_space(width: usize, height: usize) -> Grid<char> {
+    return Grid::new_filled(height, width, '.');
 }
 
-fn part2(grid: &Grid<char>, bytes_locations: &Vec<Location>) -> Location {
-    let mut lower = PART1_BYTE_COUNT;
-    let mut upper = bytes_locations.len();
-    loop {
-        let diff = upper - lower;
-        let i = lower + diff / 2;
+pub struct Solver;
 
-        // If this is the final index to check, we know it's the result.
-        if diff == 0 {
-            return bytes_locations[i];
+impl Solution for Solver {
+    type Input = Vec<Location>;
+    type Part1 = u32;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let byte_locations = process_file(input)?;
+        if byte_locations.len() < PART1_BYTE_COUNT {
+            return Err(Error::Input(format!(
+                "expected at least {PART1_BYTE_COUNT} bytes but found {}",
+                byte_locations.len()
+            )));
         }
 
-        match bfs(grid.clone(), &bytes_locations, i) {
-            None => upper = i - 1,
-            Some(_) => lower = i + 1,
+        return Ok(byte_locations);
+    }
+
+    fn part1(byte_locations: &Self::Input) -> u32 {
+        return part1(
+            &empty_memory_space(WIDTH, HEIGHT),
+            byte_locations,
+            PART1_BYTE_COUNT,
+        );
+    }
+
+    // The coordinates of the first byte that blocks the exit, formatted as "x,y"
+    fn part2(byte_locations: &Self::Input) -> String {
+        let blocker = part2(
+            &empty_memory_space(WIDTH, HEIGHT),
+            byte_locations,
+            PART1_BYTE_COUNT,
+        );
+        return match blocker {
+            Some((x, y)) => format!("{x},{y}"),
+            None => "never blocked".to_string(),
         };
     }
 }
 
-const WIDTH: usize = 71;
-const HEIGHT: usize = 71;
-const PART1_BYTE_COUNT: usize = 1024;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+";
+
+    // The example memory space is 7x7 and only the first 12 bytes fall for part 1.
+    const EXAMPLE_SIZE: usize = 7;
+    const EXAMPLE_BYTE_COUNT: usize = 12;
+
+    #[test]
+    fn parse_example() {
+        let byte_locations = process_file(EXAMPLE).unwrap();
+        assert_eq!(byte_locations.len(), 25);
+        assert_eq!(byte_locations[0], (5, 4));
+    }
+
+    #[test]
+    fn parse_rejects_bytes_outside_the_memory_space() {
+        let error = process_file("1,1\n70,71\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: 70,71 is outside the 71x71 memory space"
+        );
+    }
 
-pub fn run() {
-    let byte_locations = process_file("input/year2024/day18.txt");
+    #[test]
+    fn bfs_example() {
+        let byte_locations = process_file(EXAMPLE).unwrap();
+        let grid = empty_memory_space(EXAMPLE_SIZE, EXAMPLE_SIZE);
+        assert_eq!(
+            bfs(grid.clone(), &byte_locations, EXAMPLE_BYTE_COUNT),
+            Some(22)
+        );
+        assert_eq!(bfs(grid, &byte_locations, byte_locations.len()), None);
+    }
 
-    let grid = Grid {
-        col_count: WIDTH,
-        row_count: HEIGHT,
-        contents: vec!['.'; HEIGHT * WIDTH],
-    };
+    #[test]
+    fn part1_example() {
+        let byte_locations = process_file(EXAMPLE).unwrap();
+        let grid = empty_memory_space(EXAMPLE_SIZE, EXAMPLE_SIZE);
+        assert_eq!(part1(&grid, &byte_locations, EXAMPLE_BYTE_COUNT), 22);
+    }
 
-    let part1_result = part1(&grid, &byte_locations);
-    let part2_result = part2(&grid, &byte_locations);
+    #[test]
+    fn part2_example() {
+        let byte_locations = process_file(EXAMPLE).unwrap();
+        let grid = empty_memory_space(EXAMPLE_SIZE, EXAMPLE_SIZE);
+        assert_eq!(
+            part2(&grid, &byte_locations, EXAMPLE_BYTE_COUNT),
+            Some((6, 1))
+        );
+
+        // The bytes before the blocker leave a way to the exit.
+        assert_eq!(
+            part2(&grid, &byte_locations[..20], EXAMPLE_BYTE_COUNT),
+            None
+        );
+    }
 
-    println!("Part 1: {}", part1_result);
-    println!("Part 2: {:?}", part2_result);
+    #[test]
+    fn parse_requires_the_bytes_of_part_1() {
+        let error = Solver::parse("1,1\n2,2\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected at least 1024 bytes but found 2"
+        );
+    }
 }
@@ -1,24 +1,21 @@
-use crate::util::file::read;
+use crate::error::Error;
+use crate::solution::Solution;
+use crate::util::file;
 use std::collections::HashMap;
-fn process_file(filename: &str) -> (Vec<String>, Vec<String>) {
-    let mut input_iter = read(filename).unwrap().flatten();
+fn process_file(input: &str) -> Result<(Vec<String>, Vec<String>), Error> {
+    let (towels, designs) = file::two_sections(input, "the towel patterns", "the designs")?;
 
-    let towel_patterns = input_iter
-        .next()
-        .unwrap()
-        .split(", ")
-        .map(|input| input.to_owned())
-        .collect();
+    let [towels] = &towels[..] else {
+        return Err(towels[1].error("expected the towel patterns on a single line"));
+    };
+    let towel_patterns = file::list(towels, ", ")?;
 
-    // Skip the empty line
-    input_iter.next();
+    let desired_patterns = designs.iter().map(|line| line.text.to_string()).collect();
 
-    let desired_patterns = input_iter.map(|line| line.to_string()).collect();
-
-    return (towel_patterns, desired_patterns);
+    return Ok((towel_patterns, desired_patterns));
 }
 
-fn find_combinations(towel_patterns: Vec<String>, desired_patterns: Vec<String>) -> Vec<u64> {
+fn find_combinations(towel_patterns: &[String], desired_patterns: &[String]) -> Vec<u64> {
     return desired_patterns
         .iter()
         .map(|pattern| {
@@ -56,18 +53,18 @@ fn find_combinations(towel_patterns: Vec<String>, desired_patterns: Vec<String>)
                 }
 
                 // Add all the potential towel patterns to the queue.
-                for i in 0..towel_patterns.len() {
-                    queue.push((new_strand_string.clone(), towel_patterns[i].clone()));
+                for towel_pattern in towel_patterns {
+                    queue.push((new_strand_string.clone(), towel_pattern.clone()));
                 }
 
                 queue.sort_by(|pattern1, pattern2| {
                     (pattern2.0.len() + pattern2.1.len())
-                        .cmp(&(&pattern1.0.len() + pattern1.1.len()))
+                        .cmp(&(pattern1.0.len() + pattern1.1.len()))
                 });
             }
 
             return match visited.get(pattern) {
-                Some(x) => x.clone(),
+                Some(x) => *x,
                 None => 0,
             };
         })
@@ -75,14 +72,66 @@ fn find_combinations(towel_patterns: Vec<String>, desired_patterns: Vec<String>)
         .collect::<Vec<u64>>();
 }
 
-pub fn run() {
-    let (towel_patterns, desired_patterns) = process_file("input/year2024/day19.txt");
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = (Vec<String>, Vec<String>);
+    type Part1 = usize;
+    type Part2 = u64;
 
-    let result = find_combinations(towel_patterns, desired_patterns);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
 
-    let part1_result = result.len();
-    let part2_result: u64 = result.iter().sum();
+    // Number of designs that are possible
+    fn part1((towel_patterns, desired_patterns): &Self::Input) -> usize {
+        return find_combinations(towel_patterns, desired_patterns).len();
+    }
+
+    // Total number of ways the designs can be made
+    fn part2((towel_patterns, desired_patterns): &Self::Input) -> u64 {
+        return find_combinations(towel_patterns, desired_patterns)
+            .iter()
+            .sum();
+    }
+}
 
-    println!("Part 1: {}", part1_result);
-    println!("Part 2: {}", part2_result);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+";
+
+    #[test]
+    fn parse_example() {
+        let (towel_patterns, desired_patterns) = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            towel_patterns,
+            ["r", "wr", "b", "g", "bwu", "rb", "gb", "br"]
+        );
+        assert_eq!(desired_patterns.len(), 8);
+    }
+
+    #[test]
+    fn part1_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&input), 6);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&input), 16);
+    }
 }
@@ -1,267 +1,257 @@
-use crate::util::{file::read, grid::Grid, point::Point};
-use std::collections::{HashMap, HashSet};
-
-fn process_file(filename: &str) -> Grid<char> {
-    let input = read(filename)
-        .expect("File must have some contents")
-        .flatten()
-        .collect::<Vec<String>>();
-
-    let maze = Grid {
-        contents: input.iter().flat_map(|line| line.chars()).collect(),
-        col_count: input[0].len(),
-        row_count: input.len(),
-    };
-
-    return maze;
-}
+use crate::error::{self, Error};
+use crate::solution::Solution;
+use crate::util::grid::Grid;
+use crate::util::point::{Point, Vector};
+use crate::util::search;
+
+fn process_file(input: &str) -> Result<Grid<char>, Error> {
+    let maze = Grid::from_chars(error::numbered(input))?;
+    for (tile, name) in [('S', "start"), ('E', "end")] {
+        if maze.find_index(|&char| char == tile).is_none() {
+            return Err(Error::Input(format!(
+                "the racetrack has no {name} tile '{tile}'"
+            )));
+        }
+    }
 
-#[derive(Debug, Clone, Copy)]
-struct Location {
-    point: Point,
-    distance: u32,
+    return Ok(maze);
 }
 
-fn bfs(
-    maze: &Grid<char>,
-    start_location: Location,
-    remaining_distance_cache: &mut HashMap<Point, u32>,
-) -> Vec<Location> {
-    let mut visited: HashSet<Point> = HashSet::new();
-    let mut queue: Vec<(Location, Vec<Location>)> = Vec::new();
-
-    queue.push((start_location, vec![start_location.clone()]));
+// The tiles next to `point` that are part of the racetrack.
+fn track_neighbors(maze: &Grid<char>, point: Point) -> impl Iterator<Item = Point> + '_ {
+    return maze
+        .neighbors4(point)
+        .filter(|(_, &value)| value != '#')
+        .map(|(next, _)| next);
+}
 
-    let final_path = loop {
-        let (Location { point, distance }, mut path) =
-            queue.pop().expect("Queue must have a value");
+// The racetrack without cheating from the start to the end, along with how long it
+// takes to finish from every tile of the track. Walls can't reach the end.
+fn race(maze: &Grid<char>, start: Point) -> (Vec<Point>, Grid<Option<u32>>) {
+    let from_start = search::bfs(
+        start,
+        |&point| track_neighbors(maze, point),
+        |&point| maze[point] == 'E',
+    );
+    let end = *from_start
+        .goals
+        .first()
+        .expect("The end must be reachable from the start");
+    let track = from_start.path(end).expect("The end was reached");
+
+    let to_end = search::bfs(end, |&point| track_neighbors(maze, point), |_| false);
+    let to_end = Grid::from_fn(maze.row_count, maze.col_count, |point| {
+        return to_end.distance(&point);
+    });
 
-        if !visited.insert(point) {
-            continue;
-        }
+    return (track, to_end);
+}
 
-        if let Some(&cached_distance) = remaining_distance_cache.get(&point) {
-            let last_index = path.len() - 1;
-            let mut location = path[last_index];
-            location.distance = distance + cached_distance;
-            path[last_index] = location;
+// Whether finishing the race after cheating from `distance` picoseconds into the race
+// over to `cheat_end` saves at least `time_save` picoseconds.
+fn saves_time(
+    to_end: &Grid<Option<u32>>,
+    race_time: u32,
+    distance: u32,
+    cheat_end: Point,
+    time_save: u32,
+) -> bool {
+    return to_end[cheat_end]
+        .is_some_and(|remaining| distance + remaining + time_save <= race_time);
+}
 
-            return path;
-        }
+// Count the cheats of up to `max_length` picoseconds that save at least `time_save`.
+fn count_cheats(maze: &Grid<char>, start: Point, max_length: usize, time_save: u32) -> u32 {
+    let (track, to_end) = race(maze, start);
+    let race_time = track.len() as u32 - 1;
+    let reach = max_length as isize;
 
-        let location_value = maze[point];
+    let mut count = 0;
 
-        if location_value == 'E' {
-            break path;
-        }
+    // Get a count of all valid cheats.
+    for (distance, &point) in track.iter().enumerate() {
+        for x_offset in -reach..=reach {
+            for y_offset in -reach..=reach {
+                let offset = Vector::new(x_offset, y_offset);
+                let cheat_length = offset.manhattan(Vector::ZERO);
+
+                // There are no valid cheats with a distance over the max length.
+                if cheat_length > max_length {
+                    continue;
+                }
 
-        // Push adjacent locations
-        [
-            (point.x.checked_sub(1), Some(point.y)),
-            (Some(point.x), point.y.checked_sub(1)),
-            (point.x.checked_add(1), Some(point.y)),
-            (Some(point.x), point.y.checked_add(1)),
-        ]
-        .iter()
-        .for_each(|(x, y)| {
-            let location_value = maze.checked_get(y, x);
-
-            if location_value.is_some() && ['.', 'E'].contains(location_value.unwrap()) {
-                let new_location = Location {
-                    point: Point {
-                        x: x.unwrap(),
-                        y: y.unwrap(),
-                    },
-                    distance: distance + 1,
+                let Some((new_point, 'E' | '.')) = maze
+                    .neighbor(point, offset)
+                    .map(|(point, &value)| (point, value))
+                else {
+                    continue;
                 };
-                let mut new_path = path.clone();
-                new_path.push(new_location.clone());
 
-                queue.push((new_location, new_path))
+                let new_distance = (distance + cheat_length) as u32;
+                if saves_time(&to_end, race_time, new_distance, new_point, time_save) {
+                    count += 1;
+                }
             }
-        });
-
-        // Sort the queue by distance.
-        queue.sort_by(|(location1, _), (location2, _)| {
-            (location1.distance).cmp(&location2.distance)
-        });
-    };
-
-    // Update the cache.
-    let total_distance = final_path[final_path.len() - 1].distance;
-    final_path.iter().for_each(|Location { point, distance }| {
-        let remaining_distance = total_distance - distance;
-
-        // Get the point value from the cache, if it exists.
-        let cache_value = remaining_distance_cache.get(point);
-        if cache_value.is_none() || Some(&remaining_distance) > cache_value {
-            remaining_distance_cache.insert(point.clone(), remaining_distance);
         }
-    });
+    }
 
-    return final_path;
+    return count;
 }
 
-fn part1(maze: &Grid<char>, start_location: Location, time_save: u32) -> u32 {
-    let mut remaining_distance_cache: HashMap<Point, u32> = HashMap::new();
+// A cheat of two picoseconds can jump straight through a wall, or cut the corner where
+// the track touches itself diagonally.
+fn part1(maze: &Grid<char>, start: Point, time_save: u32) -> u32 {
+    return count_cheats(maze, start, 2, time_save);
+}
 
-    let bfs_path = bfs(maze, start_location, &mut remaining_distance_cache);
-    let bfs_distance = bfs_path[bfs_path.len() - 1].distance;
+fn part2(maze: &Grid<char>, start: Point, time_save: u32) -> u32 {
+    return count_cheats(maze, start, 20, time_save);
+}
 
-    let mut count = 0;
+fn find_start_location(maze: &Grid<char>) -> Point {
+    let (row, col) = maze
+        .find_index(|char| char == &'S')
+        .expect("The parser checks for the start");
 
-    for i in 0..bfs_path.len() {
-        let Location { point, distance } = bfs_path[i];
-
-        // Find all valid cheats
-        [
-            (
-                (point.x.checked_sub(1), Some(point.y)),
-                (point.x.checked_sub(2), Some(point.y)),
-            ),
-            (
-                (point.x.checked_add(1), Some(point.y)),
-                (point.x.checked_add(2), Some(point.y)),
-            ),
-            (
-                (Some(point.x), point.y.checked_sub(1)),
-                (Some(point.x), point.y.checked_sub(2)),
-            ),
-            (
-                (Some(point.x), point.y.checked_add(1)),
-                (Some(point.x), point.y.checked_add(2)),
-            ),
-        ]
-        .iter()
-        .for_each(|((wall_x, wall_y), (start_x, start_y))| {
-            let wall_value = maze.checked_get(wall_y, wall_x);
-            let start_value = maze.checked_get(start_y, start_x);
-
-            // We only run cheat checks when it is a valid cheat.
-            if wall_value != Some(&'#') {
-                return;
-            }
+    return Point::new(col, row);
+}
 
-            if start_value == Some(&'E') || start_value == Some(&'.') {
-                let bfs_cheat_distance = bfs(
-                    maze,
-                    Location {
-                        point: Point {
-                            x: start_x.unwrap(),
-                            y: start_y.unwrap(),
-                        },
-                        distance: distance + 2,
-                    },
-                    &mut remaining_distance_cache,
-                )
-                .pop()
-                .expect("A valid path must be found")
-                .distance;
-
-                if bfs_distance - time_save >= bfs_cheat_distance {
-                    count = count + 1;
-                }
-            }
-        });
-    }
+const TIME_SAVE: u32 = 100;
 
-    return count;
-}
+pub struct Solver;
 
-fn part2(maze: &Grid<char>, start_location: Location, time_save: u32) -> u32 {
-    let mut remaining_distance_cache: HashMap<Point, u32> = HashMap::new();
+impl Solution for Solver {
+    type Input = Grid<char>;
+    type Part1 = u32;
+    type Part2 = u32;
 
-    let bfs_path = bfs(maze, start_location, &mut remaining_distance_cache);
-    let bfs_distance = bfs_path[bfs_path.len() - 1].distance;
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
 
-    let mut count = 0;
+    fn part1(maze: &Self::Input) -> u32 {
+        return part1(maze, find_start_location(maze), TIME_SAVE);
+    }
 
-    // Get a count of all valid cheats.
-    for i in 0..bfs_path.len() {
-        let Location { point, distance } = bfs_path[i];
+    fn part2(maze: &Self::Input) -> u32 {
+        return part2(maze, find_start_location(maze), TIME_SAVE);
+    }
+}
 
-        let mut cheat_visited: HashSet<(Option<usize>, Option<usize>)> = HashSet::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::year2024::racetrack;
+    use crate::util::random::Rng;
+
+    const EXAMPLE: &str = "\
+###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+";
+
+    #[test]
+    fn parse_example() {
+        let maze = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!((maze.row_count, maze.col_count), (15, 15));
+        assert_eq!(find_start_location(&maze), Point { x: 1, y: 3 });
+    }
 
-        for x_offset in 0..21 {
-            for y_offset in 0..21 {
-                // There are no valid cheats with a distance over 20.
-                if x_offset + y_offset > 20 {
-                    continue;
-                }
+    #[test]
+    fn parse_requires_a_start_and_an_end() {
+        let error = Solver::parse("#####\n#..E#\n#####\n").unwrap_err();
+        assert_eq!(error.to_string(), "the racetrack has no start tile 'S'");
 
-                [
-                    (
-                        (point.x).checked_sub(x_offset),
-                        (point.y).checked_sub(y_offset),
-                    ),
-                    (
-                        (point.x).checked_add(x_offset),
-                        (point.y).checked_add(y_offset),
-                    ),
-                    (
-                        (point.x).checked_sub(x_offset),
-                        (point.y).checked_add(y_offset),
-                    ),
-                    (
-                        (point.x).checked_add(x_offset),
-                        (point.y).checked_sub(y_offset),
-                    ),
-                ]
-                .iter()
-                .for_each(|(new_x, new_y)| {
-                    if !cheat_visited.insert((new_x.clone(), new_y.clone())) {
-                        return;
-                    }
-
-                    let value = maze.checked_get(&new_y, &new_x);
-
-                    if value != Some(&'E') && value != Some(&'.') {
-                        return;
-                    }
-
-                    let new_distance = distance + x_offset as u32 + y_offset as u32;
-
-                    let bfs_cheat_distance = bfs(
-                        maze,
-                        Location {
-                            point: Point {
-                                x: new_x.unwrap(),
-                                y: new_y.unwrap(),
-                            },
-                            distance: new_distance,
-                        },
-                        &mut remaining_distance_cache,
-                    )
-                    .pop()
-                    .expect("A valid path must be found")
-                    .distance;
-
-                    if bfs_distance - time_save >= bfs_cheat_distance {
-                        count = count + 1;
-                    }
-                })
-            }
-        }
+        let error = Solver::parse("#####\n#S..#\n#####\n").unwrap_err();
+        assert_eq!(error.to_string(), "the racetrack has no end tile 'E'");
     }
 
-    return count;
-}
+    // The example cheats save far less time than the real ones, so count the
+    // cheats that save at least a smaller amount.
+    #[test]
+    fn part1_example() {
+        let maze = Solver::parse(EXAMPLE).unwrap();
+        let start_location = find_start_location(&maze);
+        assert_eq!(part1(&maze, start_location, 64), 1);
+        assert_eq!(part1(&maze, start_location, 20), 5);
+    }
 
-pub fn run() {
-    let maze = process_file("input/year2024/day20.txt");
+    #[test]
+    fn part2_example() {
+        let maze = Solver::parse(EXAMPLE).unwrap();
+        let start_location = find_start_location(&maze);
+        assert_eq!(part2(&maze, start_location, 76), 3);
+        assert_eq!(part2(&maze, start_location, 50), 285);
+    }
 
-    let start = maze
-        .find_index(|char| char == &'S')
-        .expect("Maze must have a start point.");
+    // The start of the track touches the end diagonally. Cutting that corner saves 8
+    // picoseconds, while the best straight jump through a wall only saves 6.
+    #[test]
+    fn part1_counts_cheats_around_a_corner() {
+        let maze =
+            Solver::parse("######\n##S###\n#..###\n#.#E.#\n#.##.#\n#....#\n######\n").unwrap();
+        let start = find_start_location(&maze);
+        assert_eq!(part1(&maze, start, 8), 1);
+        assert_eq!(part1(&maze, start, 6), 2);
+    }
 
-    let start_location = Location {
-        point: Point::new(start.1, start.0),
-        distance: 0,
-    };
+    // Count the cheats by comparing every pair of tiles on the track instead of
+    // looking around each tile. The generated tracks can touch themselves at a corner,
+    // so cheats that turn around a wall are counted too.
+    fn cheats_between_pairs(
+        maze: &Grid<char>,
+        start: Point,
+        max_length: usize,
+        time_save: u32,
+    ) -> (u32, bool) {
+        let (track, _) = race(maze, start);
+
+        let mut count = 0;
+        let mut turns_a_corner = false;
+        for (from, &cheat_start) in track.iter().enumerate() {
+            for (to, &cheat_end) in track.iter().enumerate().skip(from + 1) {
+                let length = cheat_start.manhattan(cheat_end);
+                if length <= max_length && to - from >= length + time_save as usize {
+                    count += 1;
+                    turns_a_corner |= cheat_start.x != cheat_end.x && cheat_start.y != cheat_end.y;
+                }
+            }
+        }
+        return (count, turns_a_corner);
+    }
 
-    let part1_result = part1(&maze, start_location, 100);
-    let part2_result = part2(&maze, start_location, 100);
+    #[test]
+    fn cheats_match_comparing_every_pair() {
+        let mut corner_cheats = false;
+        for seed in 0..50 {
+            let mut rng = Rng::new(seed);
+            let (cols, rows) = (rng.between(4, 24), rng.between(3, 24));
+            let maze = racetrack(&mut rng, cols, rows);
+            let start = find_start_location(&maze);
+            for time_save in [1, 2, 4, 10] {
+                let (expected, turns_a_corner) = cheats_between_pairs(&maze, start, 2, time_save);
+                assert_eq!(part1(&maze, start, time_save), expected, "{maze}");
+                corner_cheats |= turns_a_corner;
+            }
 
-    println!("Part 1: {}", part1_result);
-    println!("Part 2: {}", part2_result);
+            let (expected, _) = cheats_between_pairs(&maze, start, 20, 4);
+            assert_eq!(part2(&maze, start, 4), expected, "{maze}");
+        }
+        assert!(
+            corner_cheats,
+            "no generated track allows a cheat around a corner"
+        );
+    }
 }
@@ -1,11 +1,29 @@
-use crate::util::file::read;
+use crate::error::{self, Error};
+use crate::solution::Solution;
+use crate::util::direction::Direction;
 use std::collections::HashMap;
 
-fn process_file(filename: &str) -> Vec<String> {
-    return read(filename)
-        .expect("File must have some contents")
-        .flatten()
-        .collect::<Vec<String>>();
+// Codes are three digits followed by 'A', such as "029A".
+fn process_file(input: &str) -> Result<Vec<String>, Error> {
+    return error::numbered(input)
+        .map(|line| {
+            for (index, char) in line.text.chars().enumerate() {
+                let expected_digit = index < 3;
+                if index > 3 || (expected_digit && !char.is_ascii_digit()) {
+                    return Err(
+                        line.error_at_char(index, format!("unexpected '{char}' in door code"))
+                    );
+                }
+                if !expected_digit && char != 'A' {
+                    return Err(line.error_at_char(index, format!("expected 'A' but got '{char}'")));
+                }
+            }
+            if line.text.len() < 4 {
+                return Err(line.error("expected a door code like '029A'"));
+            }
+            return Ok(line.text.to_string());
+        })
+        .collect::<Result<Vec<String>, Error>>();
 }
 
 const NUMERIC_KEYPAD: [char; 12] = [' ', '0', 'A', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
@@ -27,7 +45,7 @@ fn find_directional_keypad_moves(
 
     let computed_sequence = ComputedSequence {
         input: input.to_string(),
-        iteration: iteration,
+        iteration,
     };
 
     // Check the cache to avoid looking up previously determined results.
@@ -66,21 +84,20 @@ fn find_directional_keypad_moves(
                     let move_right = desired_col > current_col;
                     let move_left = desired_col < current_col && index != 4;
 
-                    let priority =
-                        prioritize_vertical || (!prioritize_vertical && !move_right && !move_left);
+                    let priority = prioritize_vertical || (!move_right && !move_left);
 
                     if desired_row < current_row && priority {
                         index -= 3;
-                        intermediate.push('v');
+                        intermediate.push(Direction::Down.arrow());
                     } else if desired_row > current_row && index != 0 && priority {
                         index += 3;
-                        intermediate.push('^');
+                        intermediate.push(Direction::Up.arrow());
                     } else if move_left {
                         index -= 1;
-                        intermediate.push('<');
+                        intermediate.push(Direction::Left.arrow());
                     } else if move_right {
                         index += 1;
-                        intermediate.push('>');
+                        intermediate.push(Direction::Right.arrow());
                     }
                 }
                 intermediate.push('A');
@@ -109,7 +126,7 @@ struct ComputedSequence {
     iteration: u8,
 }
 
-fn calculate_sequence(inputs: &Vec<String>, max_iteration: u8) -> usize {
+fn calculate_sequence(inputs: &[String], max_iteration: u8) -> usize {
     let mut directional_keypad_cache: HashMap<ComputedSequence, usize> = HashMap::new();
 
     let numeric_keypad_map: HashMap<char, usize> = HashMap::from_iter(
@@ -149,21 +166,20 @@ fn calculate_sequence(inputs: &Vec<String>, max_iteration: u8) -> usize {
                                 let move_left = desired_col < current_col && index != 1;
                                 let move_right = desired_col > current_col;
 
-                                let priority = prioritize_vertical
-                                    || (!prioritize_vertical && !move_left && !move_right);
+                                let priority = prioritize_vertical || (!move_left && !move_right);
 
                                 if desired_row > current_row && priority {
                                     index += 3;
-                                    intermediate.push('^');
+                                    intermediate.push(Direction::Up.arrow());
                                 } else if desired_row < current_row && index != 3 && priority {
                                     index -= 3;
-                                    intermediate.push('v');
+                                    intermediate.push(Direction::Down.arrow());
                                 } else if move_left {
                                     index -= 1;
-                                    intermediate.push('<');
+                                    intermediate.push(Direction::Left.arrow());
                                 } else if move_right {
                                     index += 1;
-                                    intermediate.push('>');
+                                    intermediate.push(Direction::Right.arrow());
                                 }
                             }
 
@@ -194,10 +210,66 @@ fn calculate_sequence(inputs: &Vec<String>, max_iteration: u8) -> usize {
 
 const MAX_ITERATION: u8 = 25;
 
-pub fn run() {
-    let inputs = process_file("input/year2024/day21.txt");
-    let part1_result = calculate_sequence(&inputs, 2);
-    println!("Part 1: {}", part1_result);
-    let part2_result = calculate_sequence(&inputs, MAX_ITERATION);
-    println!("Part 2: {}", part2_result);
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    fn part1(inputs: &Self::Input) -> usize {
+        return calculate_sequence(inputs, 2);
+    }
+
+    fn part2(inputs: &Self::Input) -> usize {
+        return calculate_sequence(inputs, MAX_ITERATION);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+029A
+980A
+179A
+456A
+379A
+";
+
+    #[test]
+    fn parse_example() {
+        let codes = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(codes, ["029A", "980A", "179A", "456A", "379A"]);
+    }
+
+    #[test]
+    fn calculate_sequence_example() {
+        // The shortest sequence for 029A is 68 presses long.
+        assert_eq!(calculate_sequence(&["029A".to_string()], 2), 68 * 29);
+    }
+
+    #[test]
+    fn part1_example() {
+        let codes = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&codes), 126384);
+    }
+
+    #[test]
+    fn part2_example() {
+        let codes = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&codes), 154115708116294);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_codes() {
+        assert!(Solver::parse("02A\n").is_err());
+        assert!(Solver::parse("0x9A\n").is_err());
+        assert!(Solver::parse("029B\n").is_err());
+    }
 }
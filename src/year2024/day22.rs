@@ -1,12 +1,13 @@
-use crate::util::file::read;
+use crate::error::{self, Error};
+use crate::solution::Solution;
 use std::collections::{HashMap, HashSet};
 
-fn process_file(filename: &str) -> Vec<i64> {
-    return read(filename)
-        .expect("File must have some contents")
-        .flatten()
-        .map(|string| string.parse::<i64>().expect("Input must be a number"))
-        .collect::<Vec<i64>>();
+fn process_file(input: &str) -> Result<Vec<i64>, Error> {
+    return error::numbered(input)
+        .map(|line| {
+            return line.parse::<i64>(line.text);
+        })
+        .collect::<Result<Vec<i64>, Error>>();
 }
 
 fn mix(secret_number: i64, mix_number: i64) -> i64 {
@@ -39,14 +40,14 @@ fn secret_list(secret_number_input: i64) -> Vec<i64> {
     return list;
 }
 
-fn part1(input: Vec<i64>) -> i64 {
+fn part1(input: &[i64]) -> i64 {
     return input
         .iter()
         .map(|&secret_number| secret_list(secret_number)[2000])
         .sum::<i64>();
 }
 
-fn part2(input: Vec<i64>) -> i64 {
+fn part2(input: &[i64]) -> i64 {
     let lists: Vec<Vec<(i64, i64)>> = input
         .iter()
         .map(|&secret_number| secret_list(secret_number))
@@ -100,11 +101,58 @@ fn part2(input: Vec<i64>) -> i64 {
     return largest_amount;
 }
 
-pub fn run() {
-    let inputs = process_file("input/year2024/day22.txt");
-    let part1_result = part1(inputs.clone());
-    let part2_result = part2(inputs);
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    fn part1(inputs: &Self::Input) -> i64 {
+        return part1(inputs);
+    }
+
+    fn part2(inputs: &Self::Input) -> i64 {
+        return part2(inputs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART1_EXAMPLE: &str = "1\n10\n100\n2024\n";
+    const PART2_EXAMPLE: &str = "1\n2\n3\n2024\n";
 
-    println!("{}", part1_result);
-    println!("{}", part2_result);
+    #[test]
+    fn parse_example() {
+        assert_eq!(Solver::parse(PART1_EXAMPLE).unwrap(), [1, 10, 100, 2024]);
+    }
+
+    #[test]
+    fn secret_list_example() {
+        assert_eq!(
+            secret_list(123)[1..=10],
+            [
+                15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544, 12249484, 7753432,
+                5908254
+            ]
+        );
+    }
+
+    #[test]
+    fn part1_example() {
+        let input = Solver::parse(PART1_EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&input), 37327623);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Solver::parse(PART2_EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&input), 23);
+    }
 }
@@ -1,45 +1,41 @@
-use crate::util::file::read;
+use crate::error::{self, Error};
+use crate::solution::Solution;
 use std::collections::{BTreeSet, HashMap, HashSet};
 
 type Input = HashMap<String, HashSet<String>>;
 
-fn process_file(filename: &str) -> Input {
+fn process_file(input: &str) -> Result<Input, Error> {
     let mut map: HashMap<String, HashSet<String>> = HashMap::new();
 
-    read(filename)
-        .expect("File must have some contents")
-        .flatten()
-        .collect::<Vec<String>>()
-        .iter()
-        .for_each(|string| {
-            // Get the two computers that are paired together.
-            let computers = string.split('-').collect::<Vec<&str>>();
-            let computer1 = computers[0];
-            let computer2 = computers[1];
-
-            let mut set1 = if let Some(set) = map.get(computer1) {
-                set.clone()
-            } else {
-                HashSet::new()
-            };
-
-            let mut set2 = if let Some(set) = map.get(computer2) {
-                set.clone()
-            } else {
-                HashSet::new()
-            };
-
-            set1.insert(computer2.to_owned());
-            map.insert(computer1.to_owned(), set1);
-
-            set2.insert(computer1.to_owned());
-            map.insert(computer2.to_owned(), set2);
-        });
+    for line in error::numbered(input) {
+        // Get the two computers that are paired together.
+        let mut computers = line.text.split('-');
+        let computer1 = line.expect(computers.next(), "a computer name")?;
+        let computer2 = line.expect(computers.next(), "'-' followed by a computer name")?;
+
+        let mut set1 = if let Some(set) = map.get(computer1) {
+            set.clone()
+        } else {
+            HashSet::new()
+        };
+
+        let mut set2 = if let Some(set) = map.get(computer2) {
+            set.clone()
+        } else {
+            HashSet::new()
+        };
+
+        set1.insert(computer2.to_owned());
+        map.insert(computer1.to_owned(), set1);
+
+        set2.insert(computer1.to_owned());
+        map.insert(computer2.to_owned(), set2);
+    }
 
-    return map;
+    return Ok(map);
 }
 
-fn part1(input: Input) -> usize {
+fn part1(input: &Input) -> usize {
     let mut pairs = BTreeSet::new();
 
     input
@@ -51,20 +47,19 @@ fn part1(input: Input) -> usize {
         })
         // Determine if any of the computers are groups of 3.
         .for_each(|(address, value_set)| {
-            for address2 in value_set.into_iter() {
+            for address2 in value_set.iter() {
                 if let Some(set2) = input.get(address2) {
-                    for address3 in set2.into_iter() {
-                        if value_set.contains(address3) {
-                            if &address[0..1] == "t"
+                    for address3 in set2.iter() {
+                        if value_set.contains(address3)
+                            && (&address[0..1] == "t"
                                 || &address2[0..1] == "t"
-                                || &address3[0..1] == "t"
-                            {
-                                let mut set = BTreeSet::new();
-                                set.insert(address.to_string());
-                                set.insert(address2.to_string());
-                                set.insert(address3.to_string());
-                                pairs.insert(set);
-                            }
+                                || &address3[0..1] == "t")
+                        {
+                            let mut set = BTreeSet::new();
+                            set.insert(address.to_string());
+                            set.insert(address2.to_string());
+                            set.insert(address3.to_string());
+                            pairs.insert(set);
                         }
                     }
                 }
@@ -74,7 +69,7 @@ fn part1(input: Input) -> usize {
     return pairs.len();
 }
 
-fn part2(input: Input) -> String {
+fn part2(input: &Input) -> String {
     fn recursive_search(
         current_set: &mut HashSet<String>,
         potential_group_connections: &mut HashSet<String>,
@@ -114,7 +109,7 @@ fn part2(input: Input) -> String {
         .map(|(address, value_set)| {
             let mut current_set: HashSet<String> = HashSet::new();
             current_set.insert(address.to_string());
-            return recursive_search(&mut current_set, &mut value_set.clone(), &input);
+            return recursive_search(&mut current_set, &mut value_set.clone(), input);
         })
         .collect();
 
@@ -132,11 +127,84 @@ fn part2(input: Input) -> String {
     return largest_set.join(",");
 }
 
-pub fn run() {
-    let input = process_file("input/year2024/day23.txt");
-    let part1_result = part1(input.clone());
-    let part2_result = part2(input);
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Input;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    fn part1(input: &Self::Input) -> usize {
+        return part1(input);
+    }
+
+    // The password to the LAN party
+    fn part2(input: &Self::Input) -> String {
+        return part2(input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn
+";
+
+    #[test]
+    fn parse_example() {
+        let network = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(network.len(), 16);
+        assert_eq!(network["kh"].len(), 4);
+        assert!(network["kh"].contains("tc"));
+        assert!(network["tc"].contains("kh"));
+    }
+
+    #[test]
+    fn part1_example() {
+        let network = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&network), 7);
+    }
 
-    println!("{}", part1_result);
-    println!("{}", part2_result);
+    #[test]
+    fn part2_example() {
+        let network = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&network), "co,de,ka,ta");
+    }
 }
@@ -1,11 +1,13 @@
-use crate::util::file::read;
+use crate::error::Error;
+use crate::solution::Solution;
+use crate::util::file;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Gate {
-    AND,
-    OR,
-    XOR,
+    And,
+    Or,
+    Xor,
 }
 
 #[derive(Debug, Clone)]
@@ -19,73 +21,68 @@ struct LogicGate {
 type WireValues = HashMap<String, Option<u8>>;
 
 #[derive(Debug, Clone)]
-struct Input {
+pub struct Input {
     wire_values: WireValues,
     logic_gates: Vec<LogicGate>,
 }
 
-fn process_file(filename: &str) -> Input {
-    let mut input = read(filename).unwrap().flatten().collect::<Vec<String>>();
-
-    let input_split_index = input
-        .iter()
-        .enumerate()
-        .find(|(_, line)| line == &"")
-        .unwrap()
-        .0;
+fn process_file(input: &str) -> Result<Input, Error> {
+    let (initial_values, gates) = file::two_sections(input, "the wire values", "the gates")?;
 
     let mut wire_values: WireValues = HashMap::new();
-
-    let logic_gates = input
-        .split_off(input_split_index)
-        .iter()
-        // Skip the empty line so it's unparsed.
-        .skip(1)
-        .fold(vec![], |mut gate_acc, gate| {
-            let gate_parse: Vec<&str> = gate.split(" ").collect();
-
-            let input0 = gate_parse[0].to_string();
-            let input1 = gate_parse[2].to_string();
-            let output = gate_parse[4].to_string();
-
-            // Mark the wires as undetermined values.
-            wire_values.insert(input0.clone(), None);
-            wire_values.insert(input1.clone(), None);
-            wire_values.insert(output.clone(), None);
-
-            // Record the logic gate.
-            gate_acc.push(LogicGate {
-                input0,
-                gate: match gate_parse[1] {
-                    "AND" => Gate::AND,
-                    "OR" => Gate::OR,
-                    "XOR" => Gate::XOR,
-                    _ => panic!("Failed to parse gate."),
-                },
-                input1,
-                output,
-            });
-
-            return gate_acc;
+    let mut logic_gates = vec![];
+
+    for line in &gates {
+        // A gate looks like "x00 AND y00 -> z00".
+        let mut gate_parse = line.text.split(" ");
+
+        let input0 = line.expect(gate_parse.next(), "an input wire")?.to_string();
+        let gate = line.expect(gate_parse.next(), "a gate")?;
+        let input1 = line
+            .expect(gate_parse.next(), "a second input wire")?
+            .to_string();
+        line.expect(gate_parse.next().filter(|arrow| *arrow == "->"), "'->'")?;
+        let output = line
+            .expect(gate_parse.next(), "an output wire")?
+            .to_string();
+
+        // Mark the wires as undetermined values.
+        wire_values.insert(input0.clone(), None);
+        wire_values.insert(input1.clone(), None);
+        wire_values.insert(output.clone(), None);
+
+        // Record the logic gate.
+        logic_gates.push(LogicGate {
+            input0,
+            gate: match gate {
+                "AND" => Gate::And,
+                "OR" => Gate::Or,
+                "XOR" => Gate::Xor,
+                _ => {
+                    return Err(line.error_at(
+                        gate,
+                        format!("expected 'AND', 'OR' or 'XOR' but got '{gate}'"),
+                    ));
+                }
+            },
+            input1,
+            output,
         });
+    }
 
     // Populate initial wire values.
-    input.into_iter().for_each(|wire| {
-        let split_values: Vec<&str> = wire.split(": ").collect();
-        wire_values.insert(
-            split_values[0].to_string(),
-            Some(
-                split_values[1]
-                    .parse::<u8>()
-                    .expect("Input must have parsable number values."),
-            ),
-        );
-    });
+    for line in &initial_values {
+        let mut split_values = line.text.split(": ");
+        let wire = line.expect(split_values.next(), "a wire name")?;
+        let value =
+            line.parse::<u8>(line.expect(split_values.next(), "': ' followed by a value")?)?;
+        wire_values.insert(wire.to_string(), Some(value));
+    }
 
-    return Input {
-        wire_values: wire_values,
-        logic_gates: logic_gates,
-    };
+    return Ok(Input {
+        wire_values,
+        logic_gates,
+    });
 }
 
 fn calculate_wires(
@@ -95,7 +92,7 @@ fn calculate_wires(
     }: Input,
 ) -> Option<WireValues> {
     // Loop while there are unprocessed logic gates.
-    'outer: while logic_gates.len() > 0 {
+    'outer: while !logic_gates.is_empty() {
         for index in 0..logic_gates.len() {
             let logic_gate = &logic_gates[index];
 
@@ -109,19 +106,19 @@ fn calculate_wires(
 
             let gate = logic_gate.gate;
 
-            if gate == Gate::AND {
+            if gate == Gate::And {
                 if input0 == Some(1) && input1 == Some(1) {
                     wire_values.insert(logic_gate.output.clone(), Some(1));
                 } else {
                     wire_values.insert(logic_gate.output.clone(), Some(0));
                 }
-            } else if gate == Gate::OR {
+            } else if gate == Gate::Or {
                 if input0 == Some(1) || input1 == Some(1) {
                     wire_values.insert(logic_gate.output.clone(), Some(1));
                 } else {
                     wire_values.insert(logic_gate.output.clone(), Some(0));
                 }
-            } else if gate == Gate::XOR {
+            } else if gate == Gate::Xor {
                 if (input0 == Some(1) && input1 == Some(0))
                     || (input0 == Some(0) && input1 == Some(1))
                 {
@@ -142,8 +139,8 @@ fn calculate_wires(
 
 fn wire_to_binary(wire_values: &WireValues, wire_start_char: char) -> String {
     let mut output_wire_names = wire_values
-        .iter()
-        .filter_map(|(wire_name, _)| {
+        .keys()
+        .filter_map(|wire_name| {
             if wire_name.chars().collect::<Vec<char>>()[0] == wire_start_char {
                 return Some(wire_name);
             }
@@ -156,16 +153,15 @@ fn wire_to_binary(wire_values: &WireValues, wire_start_char: char) -> String {
     return output_wire_names
         .into_iter()
         .fold(String::new(), |output, value| {
-            return String::from(
-                wire_values[value]
-                    .expect("The wire must have a value at the end of the processing.")
-                    .to_string(),
-            ) + &output;
+            return wire_values[value]
+                .expect("The wire must have a value at the end of the processing.")
+                .to_string()
+                + &output;
         });
 }
 
 fn wire_to_decimal(wire_values: &WireValues, wire_start_char: char) -> u64 {
-    return u64::from_str_radix(&wire_to_binary(&wire_values, wire_start_char), 2)
+    return u64::from_str_radix(&wire_to_binary(wire_values, wire_start_char), 2)
         .expect("Output must be in a valid binary format.");
 }
 
@@ -210,16 +206,16 @@ fn part2(
         let is_z_output = output.starts_with('z');
 
         // All z outputs should come from a "XOR" gate.
-        if gate != &Gate::XOR && is_z_output && output != highest_output {
+        if gate != &Gate::Xor && is_z_output && output != highest_output {
             swap_gates.push(output.as_str());
             continue;
         }
 
         // All "AND" gates must feed into an "OR" gate.
-        if gate == &Gate::AND && input0 != "x00" {
+        if gate == &Gate::And && input0 != "x00" {
             let output_valid_use = logic_gates
                 .iter()
-                .filter(|logic_gate| logic_gate.gate == Gate::OR)
+                .filter(|logic_gate| logic_gate.gate == Gate::Or)
                 .find(
                     |LogicGate {
                          input0: sub_input0,
@@ -238,10 +234,10 @@ fn part2(
         }
 
         // "XOR" gates will never feed directly into an "OR" gate.
-        if gate == &Gate::XOR {
+        if gate == &Gate::Xor {
             let output_valid_use = logic_gates
                 .iter()
-                .filter(|logic_gate| logic_gate.gate == Gate::OR)
+                .filter(|logic_gate| logic_gate.gate == Gate::Or)
                 .find(
                     |LogicGate {
                          input0: sub_input0,
@@ -273,7 +269,7 @@ fn part2(
         );
 
         // The "XOR" gate that includes two intermediate outputs must have a "z" output.
-        if gate == &Gate::XOR && !input0_primary_input && !input1_primary_input && !is_z_output {
+        if gate == &Gate::Xor && !input0_primary_input && !input1_primary_input && !is_z_output {
             swap_gates.push(output.as_str());
             continue;
         }
@@ -284,12 +280,88 @@ fn part2(
     return swap_gates.join(",");
 }
 
-pub fn run() {
-    let input = process_file("input/year2024/day24.txt");
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Input;
+    type Part1 = u64;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    fn part1(input: &Self::Input) -> u64 {
+        return part1(input.clone());
+    }
+
+    // The sorted names of the wires that need to be swapped
+    fn part2(input: &Self::Input) -> String {
+        return part2(input.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+x00: 1
+x01: 1
+x02: 1
+y00: 0
+y01: 1
+y02: 0
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+x02 OR y02 -> z02
+";
+
+    // A two bit ripple-carry adder with the outputs of z01 and b01 swapped.
+    const SWAPPED_ADDER: &str = "\
+x00: 1
+x01: 0
+y00: 1
+y01: 0
+
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> s01
+x01 AND y01 -> a01
+s01 XOR c00 -> b01
+s01 AND c00 -> z01
+a01 OR b01 -> z02
+";
+
+    #[test]
+    fn parse_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(input.wire_values.len(), 9);
+        assert_eq!(input.wire_values["x00"], Some(1));
+        assert_eq!(input.wire_values["z00"], None);
+        assert_eq!(input.logic_gates.len(), 3);
+        assert_eq!(input.logic_gates[1].gate, Gate::Xor);
+    }
 
-    let part1_result = part1(input.clone());
-    let part2_result = part2(input);
+    #[test]
+    fn part1_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&input), 4);
+    }
 
-    println!("{}", part1_result);
-    println!("{}", part2_result);
+    #[test]
+    fn part2_finds_swapped_wires() {
+        let input = Solver::parse(SWAPPED_ADDER).unwrap();
+        assert_eq!(Solver::part2(&input), "b01,z01");
+    }
+
+    #[test]
+    fn parse_requires_a_blank_line() {
+        let error = Solver::parse("x00: 1\nx00 AND y00 -> z00\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected a blank line between the wire values and the gates"
+        );
+    }
 }
@@ -1,80 +1,60 @@
-use crate::util::file::read;
+use crate::error::Error;
+use crate::solution::Solution;
+use crate::util::file;
+use crate::util::grid::Grid;
 
-struct Input {
+#[derive(Debug)]
+pub struct Input {
     locks: Vec<[u8; 5]>,
     keys: Vec<[u8; 5]>,
 }
 
-fn process_file(filename: &str) -> Input {
-    let mut input = read(filename)
-        .unwrap()
-        .flatten()
-        .collect::<Vec<String>>()
-        .into_iter();
-
+fn process_file(input: &str) -> Result<Input, Error> {
     let mut locks: Vec<[u8; 5]> = vec![];
     let mut keys: Vec<[u8; 5]> = vec![];
 
-    loop {
-        let line1 = match input.next() {
-            Some(value) => value,
-            None => break,
-        };
-
-        let is_lock = line1 == "#####";
-
-        let mut line1 = line1.chars().into_iter();
-
-        let line2 = input.next().unwrap();
-        let mut line2 = line2.chars().into_iter();
-
-        let line3 = input.next().unwrap();
-        let mut line3 = line3.chars().into_iter();
-
-        let line4 = input.next().unwrap();
-        let mut line4 = line4.chars().into_iter();
-
-        let line5 = input.next().unwrap();
-        let mut line5 = line5.chars().into_iter();
-
-        let line6 = input.next().unwrap();
-        let mut line6 = line6.chars().into_iter();
+    for rows in file::sections(input) {
+        // Each schematic is 7 rows of 5 cells.
+        if rows.len() != 7 {
+            return Err(rows[rows.len() - 1].error(format!(
+                "expected a schematic of 7 rows but got {}",
+                rows.len()
+            )));
+        }
 
-        let line7 = input.next().unwrap();
-        let mut line7 = line7.chars().into_iter();
+        // Locks hang from a solid top row and keys rest on a solid bottom row.
+        let is_lock = rows[0].text == "#####";
+        if !is_lock && rows[6].text != "#####" {
+            return Err(rows[0]
+                .error("expected a lock with a solid top row or a key with a solid bottom row"));
+        }
 
+        if rows[0].text.chars().count() != 5 {
+            return Err(rows[0].error("expected a row of 5 '#' or '.' cells"));
+        }
+        let schematic = Grid::from_lines(rows, |cell| match cell {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(format!("expected '#' or '.' but got '{cell}'")),
+        })?;
+
+        // The height of a column doesn't count the row the lock hangs from, or the row
+        // the key rests on.
         let mut arr: [u8; 5] = [0, 0, 0, 0, 0];
-
-        for i in 0..5 {
-            arr[i] = 6 - [
-                line1.next().unwrap() == '.',
-                line2.next().unwrap() == '.',
-                line3.next().unwrap() == '.',
-                line4.next().unwrap() == '.',
-                line5.next().unwrap() == '.',
-                line6.next().unwrap() == '.',
-                line7.next().unwrap() == '.',
-            ]
-            .into_iter()
-            .fold(0, |count, bool| match bool {
-                true => count + 1,
-                false => count,
-            });
+        for (height, column) in arr.iter_mut().zip(schematic.columns()) {
+            *height = (column.filter(|&&filled| filled).count() - 1) as u8;
         }
 
         match is_lock {
             true => locks.push(arr),
             false => keys.push(arr),
         }
-
-        // Get rid of the next empty line.
-        input.next();
     }
 
-    return Input { keys, locks };
+    return Ok(Input { keys, locks });
 }
 
-fn part1(Input { keys, locks }: Input) -> u32 {
+fn part1(Input { keys, locks }: &Input) -> u32 {
     let mut count = 0;
     keys.iter().for_each(|key| {
         locks.iter().for_each(|lock| {
@@ -93,12 +73,101 @@ fn part1(Input { keys, locks }: Input) -> u32 {
     return count;
 }
 
-pub fn run() {
-    let input = process_file("input/year2024/day25.txt");
+pub struct Solver;
 
-    let part1_result = part1(input);
-    let part2_result = "Chronicle Delivered";
+impl Solution for Solver {
+    type Input = Input;
+    type Part1 = u32;
+    type Part2 = &'static str;
 
-    println!("{}", part1_result);
-    println!("{}", part2_result);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        return process_file(input);
+    }
+
+    fn part1(input: &Self::Input) -> u32 {
+        return part1(input);
+    }
+
+    // Day 25 only has a single puzzle.
+    fn part2(_input: &Self::Input) -> &'static str {
+        return "Chronicle Delivered";
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####
+";
+
+    #[test]
+    fn parse_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(input.locks, [[0, 5, 3, 4, 3], [1, 2, 0, 5, 3]]);
+        assert_eq!(
+            input.keys,
+            [[5, 0, 2, 1, 3], [4, 3, 4, 0, 2], [3, 0, 2, 0, 1]]
+        );
+    }
+
+    #[test]
+    fn part1_example() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part1(&input), 3);
+    }
+
+    #[test]
+    fn part2_is_free() {
+        let input = Solver::parse(EXAMPLE).unwrap();
+        assert_eq!(Solver::part2(&input), "Chronicle Delivered");
+    }
+
+    #[test]
+    fn parse_rejects_schematics_without_a_solid_row() {
+        let error = Solver::parse(".....\n.....\n.....\n.....\n.....\n.....\n.....\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1: expected a lock with a solid top row or a key with a solid bottom row"
+        );
+    }
 }